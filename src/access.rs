@@ -0,0 +1,37 @@
+//! Access to the data of a `MaybeOwned` keeping the lifetime of borrowed data.
+use core::ops::Deref;
+
+/// A reference to the data of a `MaybeOwned` as returned by
+/// [`MaybeOwned::access`](crate::MaybeOwned::access).
+///
+/// Unlike `Deref` this allows recovering the (longer) lifetime `'a` of
+/// borrowed data, while owned data can only be referenced for the lifetime
+/// `'s` of the `MaybeOwned` itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ref<'a, 's, T: 'a + 's> {
+    /// a reference to borrowed data, it can outlive the `MaybeOwned`
+    Long(&'a T),
+    /// a reference to owned data, it can't outlive the `MaybeOwned`
+    Short(&'s T),
+}
+
+impl<'a, 's, T> Ref<'a, 's, T> {
+    /// Returns the reference if it can outlive the `MaybeOwned` it came from.
+    pub fn long(self) -> Option<&'a T> {
+        match self {
+            Ref::Long(v) => Some(v),
+            Ref::Short(_) => None,
+        }
+    }
+}
+
+impl<T> Deref for Ref<'_, '_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Ref::Long(v) => v,
+            Ref::Short(v) => v,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! The `AsMaybeOwned` trait for generic borrowing of values.
+#[cfg(feature = "alloc")]
+use alloc::borrow::{Cow, ToOwned};
+
+use MaybeOwned;
+
+/// Types which can be borrowed as `MaybeOwned<T>`.
+///
+/// This is the borrowing counterpart to `Into<MaybeOwned<'a, T>>`, it allows
+/// API functions to accept `T`, `&T`, `MaybeOwned<T>` and `Cow<T>` by
+/// reference without consuming them.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::{AsMaybeOwned, MaybeOwned};
+///
+/// fn total_len<A: AsMaybeOwned<Vec<u8>>>(values: &[A]) -> usize {
+///     values.iter().map(|v| v.as_maybe_owned().len()).sum()
+/// }
+///
+/// let data = vec![1u8, 2];
+/// assert_eq!(total_len(&[data.clone(), vec![3]]), 3);
+/// assert_eq!(total_len(&[&data, &data]), 4);
+/// assert_eq!(total_len(&[MaybeOwned::Borrowed(&data), MaybeOwned::Owned(vec![])]), 2);
+/// ```
+pub trait AsMaybeOwned<T> {
+    /// Returns a `MaybeOwned::Borrowed` pointing to the data.
+    fn as_maybe_owned(&self) -> MaybeOwned<'_, T>;
+}
+
+impl<T> AsMaybeOwned<T> for T {
+    fn as_maybe_owned(&self) -> MaybeOwned<'_, T> {
+        MaybeOwned::Borrowed(self)
+    }
+}
+
+impl<T> AsMaybeOwned<T> for &T {
+    fn as_maybe_owned(&self) -> MaybeOwned<'_, T> {
+        MaybeOwned::Borrowed(self)
+    }
+}
+
+impl<T> AsMaybeOwned<T> for MaybeOwned<'_, T> {
+    fn as_maybe_owned(&self) -> MaybeOwned<'_, T> {
+        self.to_ref()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ToOwned<Owned = T>> AsMaybeOwned<T> for Cow<'_, T> {
+    fn as_maybe_owned(&self) -> MaybeOwned<'_, T> {
+        MaybeOwned::Borrowed(self)
+    }
+}
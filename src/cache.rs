@@ -0,0 +1,139 @@
+//! The `CacheValue` trait and a small cache built on it.
+#[cfg(feature = "std")]
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, VecDeque};
+
+use MaybeOwned;
+
+/// Values which can be stored in a cache.
+///
+/// This is implemented by `MaybeOwned` so caches can store borrowed
+/// entries and promote them to owned ones on refresh.
+pub trait CacheValue {
+    /// Called with the value when it's evicted from the cache.
+    fn on_evict(self);
+
+    /// Called when the cache entry is refreshed.
+    fn refresh(&mut self);
+}
+
+/// Dropped on eviction, borrowed data is cloned on refresh so the entry
+/// no longer depends on the lifetime of the borrow.
+impl<T: Clone> CacheValue for MaybeOwned<'_, T> {
+    fn on_evict(self) {}
+
+    fn refresh(&mut self) {
+        self.make_owned();
+    }
+}
+
+#[cfg(feature = "std")]
+/// A small in-memory cache with a fixed capacity storing owned or
+/// borrowed values.
+///
+/// If the cache is full inserting evicts the oldest entry. Borrowed
+/// entries are only cloned on the first mutable access or on refresh.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::MaybeOwnedCache;
+///
+/// let default = String::from("default");
+/// let mut cache: MaybeOwnedCache<_, String> = MaybeOwnedCache::new(2);
+/// cache.insert("a", &default);
+/// cache.insert("b", String::from("b"));
+/// assert!(!cache.get(&"a").unwrap().is_owned());
+///
+/// cache.get_mut(&"a").unwrap().push('!');
+/// assert_eq!(**cache.get(&"a").unwrap(), "default!");
+/// assert_eq!(default, "default");
+///
+/// cache.insert("c", &default);
+/// assert!(cache.get(&"a").is_none());
+/// ```
+#[derive(Debug)]
+pub struct MaybeOwnedCache<'a, K, V: 'a> {
+    capacity: usize,
+    entries: HashMap<K, MaybeOwned<'a, V>>,
+    insertion_order: VecDeque<K>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, V> MaybeOwnedCache<'a, K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Creates an empty cache holding at most `capacity` entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "cache capacity must not be 0");
+        MaybeOwnedCache {
+            capacity,
+            entries: HashMap::new(),
+            insertion_order: VecDeque::new(),
+        }
+    }
+
+    /// Returns the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts an entry, evicting the oldest entry if the cache is full.
+    ///
+    /// An existing entry for `key` is replaced, which also evicts it.
+    pub fn insert(&mut self, key: K, value: impl Into<MaybeOwned<'a, V>>) {
+        if let Some(old) = self.remove(&key) {
+            old.on_evict();
+        }
+        if self.entries.len() == self.capacity {
+            if let Some(oldest) = self.insertion_order.pop_front() {
+                if let Some(old) = self.entries.remove(&oldest) {
+                    old.on_evict();
+                }
+            }
+        }
+        self.insertion_order.push_back(key.clone());
+        self.entries.insert(key, value.into());
+    }
+
+    /// Returns the entry for `key` without cloning borrowed data.
+    pub fn get(&self, key: &K) -> Option<&MaybeOwned<'a, V>> {
+        self.entries.get(key)
+    }
+
+    /// Returns a `&mut` to the data of the entry for `key`, promoting
+    /// borrowed data to owned data first.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries.get_mut(key).map(MaybeOwned::make_owned)
+    }
+
+    /// Refreshes the entry for `key`, returns false if there is none.
+    pub fn refresh(&mut self, key: &K) -> bool {
+        match self.entries.get_mut(key) {
+            Some(value) => {
+                value.refresh();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the entry for `key` and returns it.
+    pub fn remove(&mut self, key: &K) -> Option<MaybeOwned<'a, V>> {
+        let value = self.entries.remove(key)?;
+        self.insertion_order.retain(|k| k != key);
+        Some(value)
+    }
+}
@@ -0,0 +1,103 @@
+//! The `CheckedArith`, `SaturatingArith` and `WrappingArith` traits used by
+//! the arithmetic methods of `MaybeOwned`.
+
+/// Numeric types with arithmetic operations which return `None` instead
+/// of overflowing or dividing by zero.
+///
+/// This is used by `MaybeOwned::checked_add` and related methods and is
+/// implemented for the primitive integer types.
+pub trait CheckedArith: Sized {
+    /// Checked addition, `None` on overflow.
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    /// Checked subtraction, `None` on overflow.
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+    /// Checked multiplication, `None` on overflow.
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+    /// Checked division, `None` on overflow or if `rhs` is 0.
+    fn checked_div(&self, rhs: &Self) -> Option<Self>;
+}
+
+macro_rules! checked_arith_impls {
+    ($($T:ty),*) => {$(
+        impl CheckedArith for $T {
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                <$T>::checked_add(*self, *rhs)
+            }
+
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                <$T>::checked_sub(*self, *rhs)
+            }
+
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                <$T>::checked_mul(*self, *rhs)
+            }
+
+            fn checked_div(&self, rhs: &Self) -> Option<Self> {
+                <$T>::checked_div(*self, *rhs)
+            }
+        }
+    )*};
+}
+
+/// Numeric types with arithmetic operations which saturate at the numeric
+/// bounds instead of overflowing.
+///
+/// This is used by `MaybeOwned::saturating_add` and related methods and is
+/// implemented for the primitive integer types.
+pub trait SaturatingArith {
+    /// Saturating addition.
+    fn saturating_add(&self, rhs: &Self) -> Self;
+    /// Saturating subtraction.
+    fn saturating_sub(&self, rhs: &Self) -> Self;
+    /// Saturating multiplication.
+    fn saturating_mul(&self, rhs: &Self) -> Self;
+}
+
+/// Numeric types with arithmetic operations which wrap around at the
+/// numeric bounds instead of overflowing.
+///
+/// This is used by `MaybeOwned::wrapping_add` and related methods and is
+/// implemented for the primitive integer types.
+pub trait WrappingArith {
+    /// Wrapping addition.
+    fn wrapping_add(&self, rhs: &Self) -> Self;
+    /// Wrapping subtraction.
+    fn wrapping_sub(&self, rhs: &Self) -> Self;
+    /// Wrapping multiplication.
+    fn wrapping_mul(&self, rhs: &Self) -> Self;
+}
+
+macro_rules! saturating_wrapping_arith_impls {
+    ($($T:ty),*) => {$(
+        impl SaturatingArith for $T {
+            fn saturating_add(&self, rhs: &Self) -> Self {
+                <$T>::saturating_add(*self, *rhs)
+            }
+
+            fn saturating_sub(&self, rhs: &Self) -> Self {
+                <$T>::saturating_sub(*self, *rhs)
+            }
+
+            fn saturating_mul(&self, rhs: &Self) -> Self {
+                <$T>::saturating_mul(*self, *rhs)
+            }
+        }
+
+        impl WrappingArith for $T {
+            fn wrapping_add(&self, rhs: &Self) -> Self {
+                <$T>::wrapping_add(*self, *rhs)
+            }
+
+            fn wrapping_sub(&self, rhs: &Self) -> Self {
+                <$T>::wrapping_sub(*self, *rhs)
+            }
+
+            fn wrapping_mul(&self, rhs: &Self) -> Self {
+                <$T>::wrapping_mul(*self, *rhs)
+            }
+        }
+    )*};
+}
+
+checked_arith_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+saturating_wrapping_arith_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
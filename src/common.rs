@@ -0,0 +1,67 @@
+//! The sealed `MaybeOwnedCommon` trait implemented by both enums.
+use core::ops::Deref;
+
+use {MaybeOwned, MaybeOwnedMut};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Functionality shared by `MaybeOwned` and `MaybeOwnedMut`.
+///
+/// This allows writing functions which accept either of them. The trait is
+/// sealed, so it can't be implemented outside of this crate and can be
+/// extended without a breaking change.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::{MaybeOwned, MaybeOwnedCommon, MaybeOwnedMut};
+///
+/// fn describe<M: MaybeOwnedCommon<u8>>(value: M) -> String {
+///     format!("{} {}", value.get_ref(), value.is_owned())
+/// }
+///
+/// let mut data = 12u8;
+/// assert_eq!(describe(MaybeOwned::Owned(13)), "13 true");
+/// assert_eq!(describe(MaybeOwnedMut::Borrowed(&mut data)), "12 false");
+/// ```
+pub trait MaybeOwnedCommon<T>: private::Sealed {
+    /// Returns true if the data is owned else false.
+    fn is_owned(&self) -> bool;
+
+    /// Returns the data in it's owned form, cloning borrowed data.
+    fn into_owned(self) -> T
+    where
+        T: Clone;
+
+    /// Returns a reference to the data.
+    ///
+    /// This isn't named `as_ref` to not be ambiguous with `AsRef::as_ref`.
+    fn get_ref(&self) -> &T;
+}
+
+macro_rules! common_trait_impls {
+    ($($Name:ident),*) => {$(
+        impl<T> private::Sealed for $Name<'_, T> {}
+
+        impl<T> MaybeOwnedCommon<T> for $Name<'_, T> {
+            fn is_owned(&self) -> bool {
+                $Name::is_owned(self)
+            }
+
+            fn into_owned(self) -> T
+            where
+                T: Clone,
+            {
+                $Name::into_owned(self)
+            }
+
+            fn get_ref(&self) -> &T {
+                self.deref()
+            }
+        }
+    )*};
+}
+
+common_trait_impls!(MaybeOwned, MaybeOwnedMut);
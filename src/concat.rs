@@ -0,0 +1,48 @@
+//! Concatenation of the data of multiple `MaybeOwned` values.
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use MaybeOwned;
+
+/// Collections to which a copy of another collection of the same type
+/// can be appended.
+///
+/// This is used by [`concat_maybe_owned`].
+pub trait Concat {
+    /// Appends a copy of the content of `other` to `self`.
+    fn append_copy(&mut self, other: &Self);
+}
+
+impl Concat for String {
+    fn append_copy(&mut self, other: &Self) {
+        self.push_str(other)
+    }
+}
+
+impl<T: Clone> Concat for Vec<T> {
+    fn append_copy(&mut self, other: &Self) {
+        self.extend_from_slice(other)
+    }
+}
+
+/// Concatenates the data of all `items` into a new owned value.
+///
+/// Independent of the variant the data of each item is only read, so
+/// no intermediate clones of the items are created.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::{concat_maybe_owned, MaybeOwned};
+///
+/// let world = String::from("world");
+/// let items = [MaybeOwned::Owned(String::from("hello ")), MaybeOwned::Borrowed(&world)];
+/// assert_eq!(concat_maybe_owned(&items), "hello world");
+/// ```
+pub fn concat_maybe_owned<T: Default + Concat>(items: &[MaybeOwned<'_, T>]) -> T {
+    let mut out = T::default();
+    for item in items {
+        out.append_copy(item);
+    }
+    out
+}
@@ -0,0 +1,108 @@
+//! The `DeepClone` trait used by `MaybeOwned::deep_clone`.
+use core::cell::{Cell, RefCell};
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Types which can create a copy not sharing any state with the original.
+///
+/// For most types this is the same as `Clone`, but e.g. cloning a `Rc`
+/// or `Arc` only increases the reference count while deep cloning them
+/// clones the pointed to data.
+///
+/// There is no blanket implementation for `T: Clone` as it would conflict
+/// with the implementations for `Rc` and `Arc`, instead it's implemented
+/// for common std types.
+///
+/// This is used by [`MaybeOwned::deep_clone`](crate::MaybeOwned::deep_clone).
+pub trait DeepClone {
+    /// Returns a copy not sharing any state with `self`.
+    fn deep_clone(&self) -> Self;
+}
+
+macro_rules! clone_impls {
+    ($($T:ty),*) => {$(
+        impl DeepClone for $T {
+            fn deep_clone(&self) -> Self {
+                self.clone()
+            }
+        }
+    )*};
+}
+
+clone_impls!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+#[cfg(feature = "alloc")]
+clone_impls!(String);
+
+impl<T: DeepClone> DeepClone for Option<T> {
+    fn deep_clone(&self) -> Self {
+        self.as_ref().map(T::deep_clone)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: DeepClone> DeepClone for Vec<T> {
+    fn deep_clone(&self) -> Self {
+        self.iter().map(T::deep_clone).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: DeepClone> DeepClone for Box<T> {
+    fn deep_clone(&self) -> Self {
+        Box::new((**self).deep_clone())
+    }
+}
+
+impl<T: Copy> DeepClone for Cell<T> {
+    fn deep_clone(&self) -> Self {
+        Cell::new(self.get())
+    }
+}
+
+impl<T: DeepClone> DeepClone for RefCell<T> {
+    fn deep_clone(&self) -> Self {
+        RefCell::new(self.borrow().deep_clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: DeepClone> DeepClone for Rc<T> {
+    fn deep_clone(&self) -> Self {
+        Rc::new((**self).deep_clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: DeepClone> DeepClone for Arc<T> {
+    fn deep_clone(&self) -> Self {
+        Arc::new((**self).deep_clone())
+    }
+}
@@ -2,22 +2,131 @@
 //!
 //! Take a look at their documentation for more information.
 //!
+//! # Features
+//!
+//! - `std` (default): implementations for std types like `HashMap` and
+//!   `Path`, implies `alloc`
+//! - `alloc`: implementations for `alloc` types like `Cow`, `Box`, `Rc`,
+//!   `Arc`, `String` and `Vec`
+//! - `serde`: serde `Serialize` and `Deserialize` implementations
+//! - `unstable-transitive-ops-implementations`: `std::ops` implementations
+//! - `test-util`: helpers for testing code using `MaybeOwned`
+//!
+//! Without the `std` feature the crate is `no_std`.
+//!
+#![no_std]
 #![warn(missing_docs)]
+#[cfg(feature = "std")]
+#[macro_use]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(feature = "serde")]
 extern crate serde;
 
 #[cfg(feature = "serde")]
 mod serde_impls;
+#[cfg(feature = "serde")]
+pub use serde_impls::serialize_owned;
+#[cfg(feature = "serde")]
+pub mod serde_tagged;
 
+#[cfg(feature = "unstable-transitive-ops-implementations")]
+mod ops_ext;
 #[cfg(feature = "unstable-transitive-ops-implementations")]
 mod transitive_impl;
 
-use std::borrow::{Borrow, BorrowMut, Cow};
-use std::cmp::Ordering;
-use std::fmt;
-use std::hash::{Hash, Hasher};
-use std::ops::{Deref, DerefMut};
-use std::str::FromStr;
+mod access;
+mod as_maybe_owned;
+mod cache;
+mod checked_arith;
+mod common;
+#[cfg(feature = "alloc")]
+mod concat;
+mod deep_clone;
+#[cfg(feature = "alloc")]
+mod map_keys;
+mod maybe_unsized;
+mod merge;
+mod no_clone;
+mod parts;
+mod peek;
+mod ptr_hash;
+#[cfg(any(test, feature = "test-util"))]
+mod test_util;
+#[cfg(feature = "alloc")]
+mod try_clone;
+#[cfg(feature = "alloc")]
+mod unsized_pairs;
+mod view;
+
+pub use access::Ref;
+pub use as_maybe_owned::AsMaybeOwned;
+pub use cache::CacheValue;
+#[cfg(feature = "std")]
+pub use cache::MaybeOwnedCache;
+pub use checked_arith::{CheckedArith, SaturatingArith, WrappingArith};
+pub use common::MaybeOwnedCommon;
+#[cfg(feature = "alloc")]
+pub use concat::{concat_maybe_owned, Concat};
+pub use deep_clone::DeepClone;
+#[cfg(feature = "alloc")]
+pub use map_keys::MapKeys;
+pub use maybe_unsized::MaybeOwnedUnsized;
+pub use merge::{Merge, ThreeWayMerge};
+pub use no_clone::NoClone;
+pub use parts::OwnershipParts;
+pub use peek::PeekMut;
+pub use ptr_hash::MaybeOwnedPtrHash;
+#[cfg(any(test, feature = "test-util"))]
+pub use test_util::for_each_variant;
+#[cfg(feature = "alloc")]
+pub use try_clone::TryClone;
+#[cfg(feature = "std")]
+pub use unsized_pairs::MaybeOwnedPath;
+#[cfg(feature = "alloc")]
+pub use unsized_pairs::{MaybeOwnedBytes, MaybeOwnedStr};
+pub use view::MaybeOwnedView;
+
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::mem;
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::{Cow, ToOwned};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::TryReserveError;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cell::OnceCell;
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
 
 /// This type provides a way to store data to which you either have a
 /// reference to or which you do own.
@@ -28,6 +137,16 @@ use std::str::FromStr;
 /// know about `ToOwned`. As a consequence of it can't know that `&str` should
 /// be the borrowed version of `String` and not `&String` this is especially
 /// bad wrt. `Box` as the borrowed version of `Box<T>` would be `&Box<T>`.
+/// To mitigate this `From<&'a Box<T>>`, `From<&'a Rc<T>>` and `From<&'a Arc<T>>`
+/// are implemented too, borrowing the `T` behind the pointer, and
+/// `From<Box<T>>` moves the `T` out of the box.
+///
+/// For the most common unsized types there are separate types, which can't
+/// be generic over `T` as `MaybeOwned` is: `MaybeOwnedStr` (`&str` or
+/// `String`), `MaybeOwnedBytes` (`&[u8]` or `Vec<u8>`) and
+/// `MaybeOwnedPath` (`&Path` or `PathBuf`). A `MaybeOwned<String>` can be
+/// converted into a `MaybeOwnedStr` with `From`, borrowing the `str` of
+/// borrowed data, and equivalently for the other types.
 ///
 /// While this crate has some drawbacks compared to `Cow` is has the benefit,
 /// that it works with Types which neither implement `Clone` nor `ToOwned`.
@@ -110,6 +229,39 @@ use std::str::FromStr;
 /// # #[cfg(not(feature = "serde"))] fn main() {}
 /// ```
 ///
+/// Defaults for missing fields can borrow from a `static` to avoid
+/// creating an owned default value:
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate serde_derive;
+/// # extern crate serde_json;
+/// # extern crate maybe_owned;
+/// # #[cfg(feature = "serde")]
+/// # fn main() {
+/// # use maybe_owned::MaybeOwned;
+/// static DEFAULT_NAME: String = String::new();
+///
+/// fn default_name() -> MaybeOwned<'static, String> {
+///     MaybeOwned::Borrowed(&DEFAULT_NAME)
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Config<'a> {
+///     #[serde(default = "default_name")]
+///     name: MaybeOwned<'a, String>,
+/// }
+///
+/// let config: Config = serde_json::from_str("{}").unwrap();
+/// assert!(!config.name.is_owned());
+///
+/// let config: Config = serde_json::from_str(r#"{"name": "abc"}"#).unwrap();
+/// assert!(config.name.is_owned());
+/// assert_eq!(*config.name, "abc");
+/// # }
+/// # #[cfg(not(feature = "serde"))] fn main() {}
+/// ```
+///
 /// # Transitive `std::ops` implementations
 ///
 /// There are transitive implementations for most operator in `std::ops`.
@@ -140,7 +292,8 @@ use std::str::FromStr;
 /// Adding implementations for Ops which add a `MaybeOwned` to
 /// a non `MaybeOwned` value (like `MaybeOwned<T> + T`) requires
 /// far reaching specialization in rust and is therefore not done
-/// for now.
+/// for now. Instead there are methods like `add_val` or `add_assign_val`
+/// which take the non `MaybeOwned` value and wrap it in `Owned`.
 #[derive(Debug)]
 pub enum MaybeOwned<'a, T: 'a> {
     /// owns T
@@ -171,7 +324,7 @@ pub enum MaybeOwnedMut<'a, T: 'a> {
 }
 
 macro_rules! common_impls {
-    ($Name:ident) => {
+    ($Name:ident, $BorrowedRef:ty) => {
         impl<T> $Name<'_, T> {
             /// Returns true if the data is owned else false.
             pub fn is_owned(&self) -> bool {
@@ -181,6 +334,171 @@ macro_rules! common_impls {
                 }
             }
 
+            /// Returns true if the data is borrowed else false.
+            pub fn is_borrowed(&self) -> bool {
+                !self.is_owned()
+            }
+
+            /// Returns a reference to the data if it's owned.
+            ///
+            /// This never clones or converts the data, borrowed data
+            /// results in `None`.
+            pub fn owned_ref(&self) -> Option<&T> {
+                match self {
+                    Self::Owned(v) => Some(v),
+                    Self::Borrowed(_) => None,
+                }
+            }
+
+            /// Returns a mutable reference to the data if it's owned.
+            ///
+            /// This never clones or converts the data, borrowed data
+            /// results in `None` (even for `MaybeOwnedMut`). For
+            /// `MaybeOwned` this is the same as `as_mut`.
+            pub fn as_owned_mut(&mut self) -> Option<&mut T> {
+                match self {
+                    Self::Owned(v) => Some(v),
+                    Self::Borrowed(_) => None,
+                }
+            }
+
+            /// Returns a copy of the data, like `Option::copied`.
+            pub fn copied(&self) -> T
+            where
+                T: Copy,
+            {
+                *self.deref()
+            }
+
+            /// Returns a view of the borrow state and the data.
+            ///
+            /// Unlike matching on the variants directly this doesn't depend
+            /// on the internal representation.
+            pub fn view(&self) -> MaybeOwnedView<'_, T> {
+                match self {
+                    Self::Owned(v) => MaybeOwnedView::Owned(v),
+                    Self::Borrowed(v) => MaybeOwnedView::Borrowed(v),
+                }
+            }
+
+            /// Returns a wrapper which displays the data followed by
+            /// ` (owned)` or ` (borrowed)`.
+            ///
+            /// This is meant for e.g. log messages where it's relevant if
+            /// the data is owned, the normal `Display` implementation
+            /// only displays the data.
+            pub fn display_annotated(&self) -> impl fmt::Display + '_
+            where
+                T: fmt::Display,
+            {
+                DisplayAnnotated {
+                    value: self.deref(),
+                    owned: self.is_owned(),
+                }
+            }
+
+            /// Returns a wrapper which debug formats only the data, or with
+            /// the alternate flag (`{:#?}`) the data wrapped in `Owned(..)`
+            /// or `Borrowed(..)`.
+            ///
+            /// This allows selecting if the ownership state is included in
+            /// e.g. structured logs through the format string.
+            pub fn debug(&self) -> impl fmt::Debug + '_
+            where
+                T: fmt::Debug,
+            {
+                DebugAnnotated {
+                    value: self.deref(),
+                    owned: self.is_owned(),
+                }
+            }
+
+            /// Runs `check` on the data and returns `self` if it passed.
+            ///
+            /// The data is not cloned, so this can be used with `?` in
+            /// parse-then-validate pipelines.
+            pub fn validate<E, F>(self, check: F) -> Result<Self, E>
+            where
+                F: FnOnce(&T) -> Result<(), E>,
+            {
+                check(&self)?;
+                Ok(self)
+            }
+
+            /// Compares the data of `self` and `other` using `cmp`.
+            ///
+            /// This is mainly a shortcut for use in e.g. `sort_by` closures.
+            pub fn cmp_by<F>(&self, other: &Self, cmp: F) -> Ordering
+            where
+                F: FnOnce(&T, &T) -> Ordering,
+            {
+                cmp(self, other)
+            }
+
+            /// Returns a new `MaybeOwned::Borrowed` of a value projected out of
+            /// the data by `f`, or `None` if `f` returns `None`.
+            ///
+            /// This is useful to navigate into optional nested data, e.g.
+            /// with `HashMap::get`, without cloning it.
+            pub fn project_opt<U, F>(&self, f: F) -> Option<MaybeOwned<'_, U>>
+            where
+                F: FnOnce(&T) -> Option<&U>,
+            {
+                f(self).map(MaybeOwned::Borrowed)
+            }
+
+            #[cfg(feature = "alloc")]
+            /// Returns clones of all keys of the contained map.
+            pub fn keys_cloned(&self) -> Vec<T::Key>
+            where
+                T: MapKeys,
+            {
+                MapKeys::keys_cloned(self.deref())
+            }
+
+            /// Returns the length of the data in bytes.
+            ///
+            /// Summing this up e.g. allows reserving buffer capacity before
+            /// serializing many values.
+            pub fn byte_len_hint(&self) -> usize
+            where
+                T: AsRef<[u8]>,
+            {
+                self.deref().as_ref().len()
+            }
+
+            #[cfg(feature = "alloc")]
+            /// Like `make_owned` but returns an error instead of aborting if
+            /// allocating memory for the clone of borrowed data fails.
+            ///
+            /// If an error is returned the data stays borrowed.
+            pub fn try_make_owned(&mut self) -> Result<&mut T, TryReserveError>
+            where
+                T: TryClone,
+            {
+                if let Self::Borrowed(v) = self {
+                    *self = Self::Owned(v.try_clone()?);
+                }
+                match self {
+                    Self::Owned(v) => Ok(v),
+                    Self::Borrowed(..) => unreachable!(),
+                }
+            }
+
+            /// Returns a reference to the target of the data, e.g. a `&str`
+            /// for `String` data.
+            ///
+            /// This mirrors `Option::as_deref`. As the result would always be
+            /// borrowed and `MaybeOwned` can't hold unsized types like `str`
+            /// a plain reference is returned, for sized targets it can be
+            /// turned into a `MaybeOwned::Borrowed` with `into()`.
+            pub fn as_deref(&self) -> &T::Target
+            where
+                T: Deref,
+            {
+                self.deref().deref()
+            }
+
             /// Returns a new `MaybeOwned::Borrowed` without cloning the data.
             pub fn to_ref(&self) -> MaybeOwned<'_, T> {
                 match self {
@@ -188,6 +506,101 @@ macro_rules! common_impls {
                     Self::Borrowed(v) => MaybeOwned::Borrowed(v),
                 }
             }
+
+            /// Return the contained data in it's owned form.
+            ///
+            /// If it's borrowed `clone_fn` is used to create an owned copy,
+            /// which means this does not require `T: Clone`.
+            pub fn into_owned_with<F>(self, clone_fn: F) -> T
+            where
+                F: FnOnce(&T) -> T,
+            {
+                match self {
+                    Self::Owned(v) => v,
+                    Self::Borrowed(v) => clone_fn(v),
+                }
+            }
+
+            /// Internally converts the type into it's owned variant.
+            ///
+            /// Like `make_owned` but conversion from a reference to the owned
+            /// variant is done by calling `clone_fn` instead of `Clone`.
+            ///
+            /// As with `make_owned` this also converts a `MaybeOwnedMut` to
+            /// it's owned variant, i.e. later changes will no longer affect
+            /// the source the reference pointed to.
+            pub fn make_owned_with<F>(&mut self, clone_fn: F) -> &mut T
+            where
+                F: FnOnce(&T) -> T,
+            {
+                match self {
+                    Self::Owned(v) => v,
+                    Self::Borrowed(v) => {
+                        *self = Self::Owned(clone_fn(&**v));
+                        match self {
+                            Self::Owned(v) => v,
+                            Self::Borrowed(..) => unreachable!(),
+                        }
+                    }
+                }
+            }
+        }
+
+        impl<'a, T> $Name<'a, T> {
+            /// Creates a borrowed variant, usable in `const` contexts.
+            pub const fn borrowed(v: $BorrowedRef) -> Self {
+                Self::Borrowed(v)
+            }
+
+            /// Creates an owned variant, usable in `const` contexts.
+            pub const fn owned(v: T) -> Self {
+                Self::Owned(v)
+            }
+
+            /// Applies `g` to the reference if the data is borrowed.
+            ///
+            /// Owned data is passed through unchanged. This can be used to
+            /// e.g. re-project a borrow onto a different value with the same
+            /// lifetime without touching owned data.
+            pub fn map_borrowed<G: FnOnce($BorrowedRef) -> $BorrowedRef>(self, g: G) -> Self {
+                match self {
+                    Self::Owned(v) => Self::Owned(v),
+                    Self::Borrowed(v) => Self::Borrowed(g(v)),
+                }
+            }
+
+            /// Returns the reference if the data is borrowed.
+            ///
+            /// If the data is owned it's returned as error, so it's
+            /// not lost.
+            pub fn try_into_borrowed(self) -> Result<$BorrowedRef, T> {
+                match self {
+                    Self::Owned(v) => Err(v),
+                    Self::Borrowed(v) => Ok(v),
+                }
+            }
+
+            /// Returns the data if it is owned.
+            ///
+            /// If the data is borrowed the reference is returned as error.
+            /// Unlike `into_owned` this does not require `T: Clone`.
+            pub fn try_into_owned(self) -> Result<T, $BorrowedRef> {
+                match self {
+                    Self::Owned(v) => Ok(v),
+                    Self::Borrowed(v) => Err(v),
+                }
+            }
+
+            /// Combines the data of `self` and `other` into a new owned value.
+            ///
+            /// `f` is called with references to both values, so neither of them
+            /// is cloned.
+            pub fn zip_with<B, C, F>(self, other: $Name<'a, B>, f: F) -> $Name<'a, C>
+            where
+                F: FnOnce(&T, &B) -> C,
+            {
+                $Name::Owned(f(&self, &other))
+            }
         }
 
         impl<T: Clone> $Name<'_, T> {
@@ -195,10 +608,14 @@ macro_rules! common_impls {
             ///
             /// If it's borrowed this will clone it.
             pub fn into_owned(self) -> T {
-                match self {
-                    Self::Owned(v) => v,
-                    Self::Borrowed(v) => v.clone(),
-                }
+                self.into_owned_with(T::clone)
+            }
+
+            /// Return the contained data in it's owned form converted into `U`.
+            ///
+            /// If it's borrowed this will clone it before converting it.
+            pub fn into_value<U: From<T>>(self) -> U {
+                U::from(self.into_owned())
             }
 
             /// Internally converts the type into it's owned variant.
@@ -231,16 +648,93 @@ macro_rules! common_impls {
             /// assert!(maybe.is_owned());
             /// ```
             pub fn make_owned(&mut self) -> &mut T {
+                self.make_owned_with(T::clone)
+            }
+
+            /// Moves the owned data into `buf` or clones the borrowed data into it.
+            ///
+            /// If the data is borrowed and `buf` already contains a value
+            /// `clone_from` is used, which allows reusing e.g. the allocation
+            /// of a scratch buffer.
+            pub fn clone_into_or_take(self, buf: &mut Option<T>) -> &mut T {
                 match self {
-                    Self::Owned(v) => v,
-                    Self::Borrowed(v) => {
-                        *self = Self::Owned(v.clone());
-                        match self {
-                            Self::Owned(v) => v,
-                            Self::Borrowed(..) => unreachable!(),
+                    Self::Owned(v) => buf.insert(v),
+                    Self::Borrowed(v) => match buf {
+                        Some(b) => {
+                            b.clone_from(v);
+                            b
                         }
-                    }
+                        None => buf.insert(v.clone()),
+                    },
+                }
+            }
+
+            #[cfg(feature = "alloc")]
+            /// Converts it into an `Arc`, cloning the data if it's borrowed.
+            pub fn into_shared(self) -> Arc<T> {
+                Arc::new(self.into_owned())
+            }
+
+            #[cfg(feature = "alloc")]
+            /// Returns the `Arc` in `slot`, initializing it with the data
+            /// if it's empty.
+            ///
+            /// Borrowed data is only cloned if `slot` is empty, so repeated
+            /// calls with the same `slot` share one `Arc`. If `slot` is already
+            /// initialized owned data is dropped.
+            pub fn into_arc_memoized(self, slot: &OnceCell<Arc<T>>) -> Arc<T> {
+                slot.get_or_init(|| Arc::new(self.into_owned())).clone()
+            }
+
+            #[cfg(feature = "std")]
+            /// Converts it into an `Arc` deduplicated through `pool`.
+            ///
+            /// If an equal value is already in the pool the existing `Arc`
+            /// is returned, else the data is added to the pool, cloning it
+            /// if it's borrowed.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the `pool` mutex is poisoned.
+            pub fn intern(self, pool: &Mutex<HashMap<T, Arc<T>>>) -> Arc<T>
+            where
+                T: Hash + Eq,
+            {
+                let mut pool = pool.lock().unwrap();
+                if let Some(shared) = pool.get(&*self) {
+                    return shared.clone();
                 }
+                let value = self.into_owned();
+                let shared = Arc::new(value.clone());
+                pool.insert(value, shared.clone());
+                shared
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T> $Name<'_, Vec<T>> {
+            /// Returns the sub-slice of the contained `Vec` for `range`
+            /// without cloning it.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `range` is out of bounds, like slice indexing does.
+            pub fn sub(&self, range: Range<usize>) -> &[T] {
+                &self[range]
+            }
+
+            /// Returns an iterator over `N` element array chunks of the
+            /// contained `Vec` without cloning it.
+            ///
+            /// Like `chunks_exact` trailing elements not filling a whole
+            /// chunk are skipped.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `N` is 0.
+            pub fn array_chunks<const N: usize>(&self) -> impl Iterator<Item = &[T; N]> + '_ {
+                self.chunks_exact(N)
+                    .map(|chunk| <&[T; N]>::try_from(chunk).unwrap())
             }
         }
 
@@ -255,6 +749,14 @@ macro_rules! common_impls {
             }
         }
 
+        impl<T: Index<Idx>, Idx> Index<Idx> for $Name<'_, T> {
+            type Output = T::Output;
+
+            fn index(&self, index: Idx) -> &T::Output {
+                self.deref().index(index)
+            }
+        }
+
         impl<T> AsRef<T> for $Name<'_, T> {
             fn as_ref(&self) -> &T {
                 self
@@ -267,6 +769,13 @@ macro_rules! common_impls {
             }
         }
 
+        /// Collects into the owned variant.
+        impl<A, T: FromIterator<A>> FromIterator<A> for $Name<'_, T> {
+            fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+                Self::Owned(T::from_iter(iter))
+            }
+        }
+
         impl<T> Borrow<T> for $Name<'_, T> {
             fn borrow(&self) -> &T {
                 self
@@ -325,11 +834,156 @@ macro_rules! common_impls {
                 }
             }
         }
+
+        #[cfg(feature = "std")]
+        impl<T: Error> Error for $Name<'_, T> {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                self.deref().source()
+            }
+
+            #[allow(deprecated)]
+            fn description(&self) -> &str {
+                self.deref().description()
+            }
+        }
+    };
+}
+
+common_impls!(MaybeOwned, &'a T);
+common_impls!(MaybeOwnedMut, &'a mut T);
+
+macro_rules! cross_cmp_impls {
+    ($L:ident, $R:ident) => {
+        impl<'b, A: PartialEq<B>, B> PartialEq<$R<'b, B>> for $L<'_, A> {
+            #[inline]
+            fn eq(&self, other: &$R<'b, B>) -> bool {
+                PartialEq::eq(self.deref(), other.deref())
+            }
+        }
+
+        impl<'b, A: PartialOrd<B>, B> PartialOrd<$R<'b, B>> for $L<'_, A> {
+            #[inline]
+            fn partial_cmp(&self, other: &$R<'b, B>) -> Option<Ordering> {
+                PartialOrd::partial_cmp(self.deref(), other.deref())
+            }
+        }
     };
 }
 
-common_impls!(MaybeOwned);
-common_impls!(MaybeOwnedMut);
+cross_cmp_impls!(MaybeOwned, MaybeOwnedMut);
+cross_cmp_impls!(MaybeOwnedMut, MaybeOwned);
+
+// A generic `PartialEq<T> for $Name<'_, T>` would overlap with the
+// `PartialEq<$Name<'b, B>>` implementation (for `T = $Name<'b, B>`), so
+// comparisons with the bare value are implemented for common types only,
+// the same applies to `PartialOrd`.
+macro_rules! bare_value_impls {
+    ($Name:ident; $($T:ty),*) => ($(
+        impl PartialEq<$T> for $Name<'_, $T> {
+            #[inline]
+            fn eq(&self, other: &$T) -> bool {
+                PartialEq::eq(self.deref(), other)
+            }
+        }
+
+        impl PartialEq<$Name<'_, $T>> for $T {
+            #[inline]
+            fn eq(&self, other: &$Name<'_, $T>) -> bool {
+                PartialEq::eq(self, other.deref())
+            }
+        }
+
+        impl PartialOrd<$T> for $Name<'_, $T> {
+            #[inline]
+            fn partial_cmp(&self, other: &$T) -> Option<Ordering> {
+                PartialOrd::partial_cmp(self.deref(), other)
+            }
+        }
+
+        impl PartialOrd<$Name<'_, $T>> for $T {
+            #[inline]
+            fn partial_cmp(&self, other: &$Name<'_, $T>) -> Option<Ordering> {
+                PartialOrd::partial_cmp(self, other.deref())
+            }
+        }
+    )*);
+}
+
+macro_rules! bare_value_impls_for {
+    ($($Name:ident),*) => ($(
+        bare_value_impls!($Name;
+            bool, char, u8, u16, u32, u64, u128, usize,
+            i8, i16, i32, i64, i128, isize, f32, f64
+        );
+    )*);
+}
+
+bare_value_impls_for!(MaybeOwned, MaybeOwnedMut);
+
+#[cfg(feature = "alloc")]
+macro_rules! string_value_impls_for {
+    ($($Name:ident),*) => ($(
+        bare_value_impls!($Name; String);
+
+        impl PartialEq<str> for $Name<'_, String> {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                PartialEq::eq(self.as_str(), other)
+            }
+        }
+
+        impl<'b> PartialEq<&'b str> for $Name<'_, String> {
+            #[inline]
+            fn eq(&self, other: &&'b str) -> bool {
+                PartialEq::eq(self.as_str(), *other)
+            }
+        }
+
+        impl PartialOrd<str> for $Name<'_, String> {
+            #[inline]
+            fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+                PartialOrd::partial_cmp(self.as_str(), other)
+            }
+        }
+
+        impl<'b> PartialOrd<&'b str> for $Name<'_, String> {
+            #[inline]
+            fn partial_cmp(&self, other: &&'b str) -> Option<Ordering> {
+                PartialOrd::partial_cmp(self.as_str(), *other)
+            }
+        }
+    )*);
+}
+
+#[cfg(feature = "alloc")]
+string_value_impls_for!(MaybeOwned, MaybeOwnedMut);
+
+struct DisplayAnnotated<'s, T: 's> {
+    value: &'s T,
+    owned: bool,
+}
+
+impl<T: fmt::Display> fmt::Display for DisplayAnnotated<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = if self.owned { "owned" } else { "borrowed" };
+        write!(f, "{} ({})", self.value, state)
+    }
+}
+
+struct DebugAnnotated<'s, T: 's> {
+    value: &'s T,
+    owned: bool,
+}
+
+impl<T: fmt::Debug> fmt::Debug for DebugAnnotated<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() {
+            return self.value.fmt(f);
+        }
+        let state = if self.owned { "Owned" } else { "Borrowed" };
+        f.debug_tuple(state).field(self.value).finish()
+    }
+}
 
 impl<'a, T> From<&'a T> for MaybeOwned<'a, T> {
     fn from(v: &'a T) -> Self {
@@ -337,12 +991,48 @@ impl<'a, T> From<&'a T> for MaybeOwned<'a, T> {
     }
 }
 
+/// Reborrows the mutable reference immutably.
+impl<'a, T> From<&'a mut T> for MaybeOwned<'a, T> {
+    fn from(v: &'a mut T) -> Self {
+        Self::Borrowed(v)
+    }
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! smart_pointer_from_impls {
+    ($($Ptr:ident),*) => ($(
+        impl<'a, T> From<&'a $Ptr<T>> for MaybeOwned<'a, T> {
+            fn from(v: &'a $Ptr<T>) -> Self {
+                Self::Borrowed(v)
+            }
+        }
+    )*);
+}
+
+#[cfg(feature = "alloc")]
+smart_pointer_from_impls!(Box, Rc, Arc);
+
+#[cfg(feature = "alloc")]
+/// Consumes the box, moving the data out of it into `Owned`.
+impl<T> From<Box<T>> for MaybeOwned<'_, T> {
+    fn from(v: Box<T>) -> Self {
+        Self::Owned(*v)
+    }
+}
+
 impl<'a, T> From<&'a mut T> for MaybeOwnedMut<'a, T> {
     fn from(v: &'a mut T) -> Self {
         Self::Borrowed(v)
     }
 }
 
+impl<'a, T> From<MaybeOwnedMut<'a, T>> for MaybeOwned<'a, T> {
+    fn from(v: MaybeOwnedMut<'a, T>) -> Self {
+        v.into_immutable()
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl<'a, T: ToOwned<Owned = T>> From<Cow<'a, T>> for MaybeOwned<'a, T> {
     fn from(cow: Cow<'a, T>) -> MaybeOwned<'a, T> {
         match cow {
@@ -352,6 +1042,8 @@ impl<'a, T: ToOwned<Owned = T>> From<Cow<'a, T>> for MaybeOwned<'a, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
+/// Moves owned data and keeps borrowed data borrowed, nothing is cloned.
 impl<'a, T: ToOwned<Owned = T>> From<MaybeOwned<'a, T>> for Cow<'a, T> {
     fn from(container: MaybeOwned<'a, T>) -> Cow<'a, T> {
         match container {
@@ -361,16 +1053,96 @@ impl<'a, T: ToOwned<Owned = T>> From<MaybeOwned<'a, T>> for Cow<'a, T> {
     }
 }
 
-impl<T: Clone> Clone for MaybeOwned<'_, T> {
-    fn clone(&self) -> Self {
-        match self {
-            Self::Owned(v) => Self::Owned(v.clone()),
-            Self::Borrowed(v) => Self::Borrowed(v),
-        }
+#[cfg(feature = "alloc")]
+/// Moves owned data and **clones** mutably borrowed data, so the result is
+/// always `Cow::Owned`.
+///
+/// Use `into_immutable()` and convert the resulting `MaybeOwned` instead to
+/// reborrow the data as `Cow::Borrowed` without cloning it.
+impl<'a, T: ToOwned<Owned = T>> From<MaybeOwnedMut<'a, T>> for Cow<'a, T> {
+    fn from(container: MaybeOwnedMut<'a, T>) -> Cow<'a, T> {
+        match container {
+            MaybeOwnedMut::Owned(v) => Cow::Owned(v),
+            MaybeOwnedMut::Borrowed(v) => Cow::Owned((*v).to_owned()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> MaybeOwned<'a, String> {
+    /// Converts it into a `Cow<str>`, borrowing the `str` if it's borrowed.
+    ///
+    /// The generic `Cow` conversion would produce a `Cow<String>` instead.
+    pub fn into_cow(self) -> Cow<'a, str> {
+        match self {
+            MaybeOwned::Owned(v) => Cow::Owned(v),
+            MaybeOwned::Borrowed(v) => Cow::Borrowed(v),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Clone> MaybeOwned<'a, Vec<T>> {
+    /// Converts it into a `Cow<[T]>`, borrowing the slice if it's borrowed.
+    ///
+    /// The generic `Cow` conversion would produce a `Cow<Vec<T>>` instead.
+    pub fn into_cow(self) -> Cow<'a, [T]> {
+        match self {
+            MaybeOwned::Owned(v) => Cow::Owned(v),
+            MaybeOwned::Borrowed(v) => Cow::Borrowed(v),
+        }
+    }
+
+    /// Converts it into a boxed slice, cloning the data if it's borrowed.
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        match self {
+            MaybeOwned::Owned(v) => v.into_boxed_slice(),
+            MaybeOwned::Borrowed(v) => v.as_slice().into(),
+        }
+    }
+}
+
+impl<T: Clone> Clone for MaybeOwned<'_, T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Owned(v) => Self::Owned(v.clone()),
+            Self::Borrowed(v) => Self::Borrowed(v),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        match (self, source) {
+            (Self::Owned(v), Self::Owned(s)) => v.clone_from(s),
+            (this, source) => *this = source.clone(),
+        }
+    }
+}
+
+/// Iterates over the owned data, borrowed data is cloned first.
+///
+/// To iterate without cloning iterate over a `&MaybeOwned` instead.
+impl<T: IntoIterator + Clone> IntoIterator for MaybeOwned<'_, T> {
+    type Item = T::Item;
+    type IntoIter = T::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_owned().into_iter()
     }
 }
 
-impl<T> MaybeOwned<'_, T> {
+impl<'s, T> IntoIterator for &'s MaybeOwned<'_, T>
+where
+    &'s T: IntoIterator,
+{
+    type Item = <&'s T as IntoIterator>::Item;
+    type IntoIter = <&'s T as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref().into_iter()
+    }
+}
+
+impl<'a, T> MaybeOwned<'a, T> {
     /// Returns a `&mut` if possible.
     ///
     /// If the internal representation is borrowed (`&T`) then
@@ -381,6 +1153,466 @@ impl<T> MaybeOwned<'_, T> {
             MaybeOwned::Borrowed(_) => None,
         }
     }
+
+    /// Internally converts it into it's owned variant and returns a shared
+    /// reference to the owned data.
+    ///
+    /// This is `&*self.make_owned()`, it exists for caches which promote
+    /// borrowed entries on first use and then only read them: the intent is
+    /// visible at the call site and no `&mut T` is handed out which could
+    /// be used to accidentally modify the cached value.
+    pub fn promote(&mut self) -> &T
+    where
+        T: Clone,
+    {
+        self.make_owned()
+    }
+
+    /// Returns a `&mut` to the owned data, replacing borrowed data with
+    /// an owned default value.
+    ///
+    /// Unlike `make_owned` this discards borrowed data instead of
+    /// cloning it, so it doesn't require `T: Clone`.
+    pub fn owned_or_default(&mut self) -> &mut T
+    where
+        T: Default,
+    {
+        if let MaybeOwned::Borrowed(_) = self {
+            *self = MaybeOwned::Owned(T::default());
+        }
+        match self {
+            MaybeOwned::Owned(v) => v,
+            MaybeOwned::Borrowed(..) => unreachable!(),
+        }
+    }
+
+    /// Converts it into it's owned variant, applies `f` to the owned
+    /// data and returns a shared reference to the updated data.
+    ///
+    /// Borrowed data is cloned once before `f` is applied, owned data
+    /// is updated in place.
+    pub fn update<F>(&mut self, f: F) -> &T
+    where
+        T: Clone,
+        F: FnOnce(&mut T),
+    {
+        let value = self.make_owned();
+        f(value);
+        value
+    }
+
+    /// Applies `f` to the data in place, cloning it first if it's
+    /// borrowed.
+    ///
+    /// Like `update` but for purely side-effecting transformations,
+    /// the name is meant to make the clone-on-borrow cost visible.
+    /// Owned data is never cloned, borrowed data is cloned exactly
+    /// once.
+    pub fn transform<F>(&mut self, f: F)
+    where
+        T: Clone,
+        F: FnOnce(&mut T),
+    {
+        self.update(f);
+    }
+
+    /// Borrows the given data or creates an owned default value if there is none.
+    ///
+    /// This is e.g. useful for layered configurations where an optional
+    /// borrowed override falls back to an owned default.
+    pub fn borrow_or_default(opt: Option<&'a T>) -> Self
+    where
+        T: Default,
+    {
+        match opt {
+            Some(v) => MaybeOwned::Borrowed(v),
+            None => MaybeOwned::Owned(T::default()),
+        }
+    }
+
+    /// Creates an owned value using `f`.
+    ///
+    /// There is no lazy variant which defers calling `f` until the first
+    /// deref, but the construction of an owned value can be deferred by
+    /// borrowing a lazily created shared value instead and only promoting
+    /// it to an owned value (by cloning it) once it's mutated:
+    ///
+    /// ```
+    /// # use maybe_owned::MaybeOwned;
+    /// use std::cell::OnceCell;
+    ///
+    /// let shared_default = OnceCell::new();
+    /// let mut maybe = MaybeOwned::Borrowed(shared_default.get_or_init(|| vec![1u8]));
+    /// assert!(!maybe.is_owned());
+    ///
+    /// maybe.make_owned().push(2);
+    /// assert_eq!(*maybe, vec![1, 2]);
+    /// ```
+    ///
+    /// Use `borrow_or_else` to only create an owned value if there is
+    /// no data to borrow.
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: FnOnce() -> T,
+    {
+        MaybeOwned::Owned(f())
+    }
+
+    /// Borrows the given data or creates an owned value using `f` if
+    /// there is none.
+    ///
+    /// Like `borrow_or_default` but `f` is only called if `opt` is `None`.
+    pub fn borrow_or_else<F>(opt: Option<&'a T>, f: F) -> Self
+    where
+        F: FnOnce() -> T,
+    {
+        match opt {
+            Some(v) => MaybeOwned::Borrowed(v),
+            None => MaybeOwned::Owned(f()),
+        }
+    }
+
+    /// Merges `other` into the data.
+    ///
+    /// The result is always owned, borrowed data is cloned before
+    /// merging `other` into it (which is cloned too if borrowed).
+    pub fn merge(mut self, other: MaybeOwned<'a, T>) -> Self
+    where
+        T: Merge + Clone,
+    {
+        self.make_owned().merge(other.into_owned());
+        self
+    }
+
+    /// Merges the changes `other` made relative to `base` into the
+    /// changes `self_` made relative to it.
+    ///
+    /// The result is always owned, borrowed data of `self_` is cloned
+    /// before merging into it.
+    pub fn reconcile(base: &T, self_: MaybeOwned<'a, T>, other: MaybeOwned<'a, T>) -> Self
+    where
+        T: ThreeWayMerge + Clone,
+    {
+        let mut out = self_;
+        out.make_owned().three_way_merge(base, &other);
+        out
+    }
+
+    /// Returns a guard which gives shared access to the data without cloning
+    /// it and mutable access on demand.
+    ///
+    /// This is useful for code which first needs to read the data to decide
+    /// if it needs to be changed, as only the changing code path will clone it.
+    pub fn peek_mut(&mut self) -> PeekMut<'_, 'a, T> {
+        PeekMut::new(self)
+    }
+
+    /// Returns a reference to the data which keeps the lifetime `'a`
+    /// if the data is borrowed.
+    ///
+    /// This is useful if a reference which can outlive `self` is
+    /// needed in case it's available, which `Deref` can't express.
+    pub fn access<'s>(&'s self) -> Ref<'a, 's, T> {
+        match self {
+            MaybeOwned::Owned(v) => Ref::Short(v),
+            MaybeOwned::Borrowed(v) => Ref::Long(v),
+        }
+    }
+
+    /// Returns an owned copy of the data which doesn't share any state with it.
+    ///
+    /// Unlike `clone` this is a deep copy even for data like `Rc`, where
+    /// cloning only increases the reference count.
+    pub fn deep_clone(&self) -> MaybeOwned<'static, T>
+    where
+        T: DeepClone + 'static,
+    {
+        MaybeOwned::Owned(self.deref().deep_clone())
+    }
+
+    /// Returns an owned clone of the data which no longer depends on the
+    /// lifetime `'a`, e.g. to cache a snapshot of it.
+    pub fn cloned(&self) -> MaybeOwned<'static, T>
+    where
+        T: Clone + 'static,
+    {
+        MaybeOwned::Owned(self.deref().clone())
+    }
+
+    /// Decomposes it into the ownership flag and the owned data or
+    /// borrowed reference.
+    ///
+    /// This is meant for custom encoders, see [`OwnershipParts`].
+    pub fn into_parts(self) -> OwnershipParts<'a, T> {
+        match self {
+            MaybeOwned::Owned(v) => OwnershipParts::from_owned(v),
+            MaybeOwned::Borrowed(v) => OwnershipParts::from_borrowed(v),
+        }
+    }
+
+    /// Reconstructs a `MaybeOwned` from the parts returned by `into_parts`.
+    pub fn from_parts(parts: OwnershipParts<'a, T>) -> Self {
+        match parts.into_result() {
+            Ok(v) => MaybeOwned::Owned(v),
+            Err(v) => MaybeOwned::Borrowed(v),
+        }
+    }
+
+    /// Makes it borrow `new`, independent of the previous variant.
+    ///
+    /// Any owned data is dropped, this is e.g. useful to switch the
+    /// buffer borrowed from when double-buffering.
+    pub fn relink(&mut self, new: &'a T) {
+        *self = MaybeOwned::Borrowed(new);
+    }
+
+    /// Makes it borrow `new` and returns the previous owned data, or
+    /// `None` if it was borrowed.
+    ///
+    /// Unlike `relink` this allows reusing the owned data, e.g. to recycle
+    /// it's allocation.
+    pub fn swap_to_borrow(&mut self, new: &'a T) -> Option<T> {
+        match mem::replace(self, MaybeOwned::Borrowed(new)) {
+            MaybeOwned::Owned(v) => Some(v),
+            MaybeOwned::Borrowed(_) => None,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Returns a `Cow::Borrowed` of the data without cloning it.
+    ///
+    /// To convert it into a `Cow` keeping owned data owned use `Cow::from`,
+    /// to get a `Cow` independent of any borrow use `into_detached_cow`.
+    pub fn borrow_cow_view(&self) -> Cow<'_, T>
+    where
+        T: Clone,
+    {
+        Cow::Borrowed(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Converts it into a `Cow::Owned`, cloning the data if it's borrowed.
+    ///
+    /// The result no longer depends on the lifetime `'a`.
+    pub fn into_detached_cow(self) -> Cow<'static, T>
+    where
+        T: Clone + 'static,
+    {
+        Cow::Owned(self.into_owned())
+    }
+
+    /// Returns it unchanged with the (shorter) lifetime `'b`, which
+    /// borrowed data must outlive.
+    ///
+    /// This doesn't do anything at runtime, it only makes the required
+    /// lifetime relationship explicit. If the borrowed data doesn't live
+    /// long enough the compiler error points at this call, instead of some
+    /// later use of the value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use maybe_owned::MaybeOwned;
+    ///
+    /// fn keep<'b>(data: &'b u8) -> MaybeOwned<'b, u8> {
+    ///     MaybeOwned::Borrowed(data).assert_outlives::<'b>()
+    /// }
+    /// # assert_eq!(*keep(&1), 1);
+    /// ```
+    pub fn assert_outlives<'b>(self) -> MaybeOwned<'b, T>
+    where
+        'a: 'b,
+    {
+        self
+    }
+
+    /// Maps the data with `f` into a new owned value.
+    ///
+    /// Owned data is moved into `f`, borrowed data is cloned first.
+    pub fn map<U, F>(self, f: F) -> MaybeOwned<'a, U>
+    where
+        T: Clone,
+        F: FnOnce(T) -> U,
+    {
+        MaybeOwned::Owned(f(self.into_owned()))
+    }
+
+    /// Returns a new `MaybeOwned::Borrowed` of the reference returned by `f`.
+    ///
+    /// Nothing is cloned, use `map` to create a new owned value instead.
+    pub fn map_ref<U, F>(&self, f: F) -> MaybeOwned<'_, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        MaybeOwned::Borrowed(f(self))
+    }
+
+    /// Adds `rhs`, returning `None` on overflow.
+    pub fn checked_add(self, rhs: MaybeOwned<'a, T>) -> Option<Self>
+    where
+        T: CheckedArith,
+    {
+        CheckedArith::checked_add(self.deref(), rhs.deref()).map(MaybeOwned::Owned)
+    }
+
+    /// Subtracts `rhs`, returning `None` on overflow.
+    pub fn checked_sub(self, rhs: MaybeOwned<'a, T>) -> Option<Self>
+    where
+        T: CheckedArith,
+    {
+        CheckedArith::checked_sub(self.deref(), rhs.deref()).map(MaybeOwned::Owned)
+    }
+
+    /// Multiplies by `rhs`, returning `None` on overflow.
+    pub fn checked_mul(self, rhs: MaybeOwned<'a, T>) -> Option<Self>
+    where
+        T: CheckedArith,
+    {
+        CheckedArith::checked_mul(self.deref(), rhs.deref()).map(MaybeOwned::Owned)
+    }
+
+    /// Divides by `rhs`, returning `None` on overflow or if `rhs` is 0.
+    pub fn checked_div(self, rhs: MaybeOwned<'a, T>) -> Option<Self>
+    where
+        T: CheckedArith,
+    {
+        CheckedArith::checked_div(self.deref(), rhs.deref()).map(MaybeOwned::Owned)
+    }
+
+    /// Adds `rhs`, saturating at the numeric bounds.
+    pub fn saturating_add(self, rhs: MaybeOwned<'a, T>) -> Self
+    where
+        T: SaturatingArith,
+    {
+        MaybeOwned::Owned(SaturatingArith::saturating_add(self.deref(), rhs.deref()))
+    }
+
+    /// Subtracts `rhs`, saturating at the numeric bounds.
+    pub fn saturating_sub(self, rhs: MaybeOwned<'a, T>) -> Self
+    where
+        T: SaturatingArith,
+    {
+        MaybeOwned::Owned(SaturatingArith::saturating_sub(self.deref(), rhs.deref()))
+    }
+
+    /// Multiplies by `rhs`, saturating at the numeric bounds.
+    pub fn saturating_mul(self, rhs: MaybeOwned<'a, T>) -> Self
+    where
+        T: SaturatingArith,
+    {
+        MaybeOwned::Owned(SaturatingArith::saturating_mul(self.deref(), rhs.deref()))
+    }
+
+    /// Adds `rhs`, wrapping around at the numeric bounds.
+    pub fn wrapping_add(self, rhs: MaybeOwned<'a, T>) -> Self
+    where
+        T: WrappingArith,
+    {
+        MaybeOwned::Owned(WrappingArith::wrapping_add(self.deref(), rhs.deref()))
+    }
+
+    /// Subtracts `rhs`, wrapping around at the numeric bounds.
+    pub fn wrapping_sub(self, rhs: MaybeOwned<'a, T>) -> Self
+    where
+        T: WrappingArith,
+    {
+        MaybeOwned::Owned(WrappingArith::wrapping_sub(self.deref(), rhs.deref()))
+    }
+
+    /// Multiplies by `rhs`, wrapping around at the numeric bounds.
+    pub fn wrapping_mul(self, rhs: MaybeOwned<'a, T>) -> Self
+    where
+        T: WrappingArith,
+    {
+        MaybeOwned::Owned(WrappingArith::wrapping_mul(self.deref(), rhs.deref()))
+    }
+
+    /// Takes the value out if `pred` returns true for the data, leaving
+    /// an owned default value in it's place.
+    ///
+    /// If `pred` returns false `self` is left unchanged and `None` is
+    /// returned. Like `Option::take_if` but nothing is cloned.
+    pub fn take_if<F>(&mut self, pred: F) -> Option<MaybeOwned<'a, T>>
+    where
+        T: Default,
+        F: FnOnce(&T) -> bool,
+    {
+        if pred(self) {
+            Some(mem::replace(self, MaybeOwned::Owned(T::default())))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns a reference to the data of the first owned item.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::{first_owned, MaybeOwned};
+///
+/// let data = 1u8;
+/// let items = [MaybeOwned::Borrowed(&data), MaybeOwned::Owned(2), MaybeOwned::Owned(3)];
+/// assert_eq!(first_owned(&items), Some(&2));
+/// ```
+pub fn first_owned<'s, T>(items: &'s [MaybeOwned<'_, T>]) -> Option<&'s T> {
+    items.iter().find_map(|item| match item {
+        MaybeOwned::Owned(v) => Some(v),
+        MaybeOwned::Borrowed(_) => None,
+    })
+}
+
+/// Returns the fraction of owned items, `0.0` if `items` is empty.
+///
+/// This is e.g. useful to monitor how much of a working set has been
+/// cloned from borrowed data.
+pub fn owned_fraction<T>(items: &[MaybeOwned<'_, T>]) -> f64 {
+    if items.is_empty() {
+        return 0.0;
+    }
+    let owned = items.iter().filter(|item| item.is_owned()).count();
+    owned as f64 / items.len() as f64
+}
+
+/// Returns the first owned candidate, or if there is none the first
+/// borrowed candidate.
+///
+/// This is e.g. useful for layered configurations where owned overrides
+/// take priority over borrowed defaults.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::{coalesce, MaybeOwned};
+///
+/// let default = 1u8;
+/// let layers = vec![MaybeOwned::Borrowed(&default), MaybeOwned::Owned(2)];
+/// assert_eq!(coalesce(layers), Some(MaybeOwned::Owned(2)));
+/// ```
+pub fn coalesce<'a, T, I>(candidates: I) -> Option<MaybeOwned<'a, T>>
+where
+    I: IntoIterator<Item = MaybeOwned<'a, T>>,
+{
+    let mut first_borrowed = None;
+    for candidate in candidates {
+        match candidate {
+            MaybeOwned::Owned(_) => return Some(candidate),
+            MaybeOwned::Borrowed(_) => {
+                first_borrowed.get_or_insert(candidate);
+            }
+        }
+    }
+    first_borrowed
+}
+
+#[cfg(feature = "std")]
+/// Returns the number of owned values in `map`.
+///
+/// This is e.g. useful to monitor how many entries of a cache have been
+/// cloned from borrowed data.
+pub fn count_owned_values<K, V, S>(map: &HashMap<K, MaybeOwned<'_, V>, S>) -> usize {
+    map.values().filter(|v| v.is_owned()).count()
 }
 
 impl<T> DerefMut for MaybeOwnedMut<'_, T> {
@@ -392,6 +1624,51 @@ impl<T> DerefMut for MaybeOwnedMut<'_, T> {
     }
 }
 
+impl<T: IndexMut<Idx>, Idx> IndexMut<Idx> for MaybeOwnedMut<'_, T> {
+    fn index_mut(&mut self, index: Idx) -> &mut T::Output {
+        self.deref_mut().index_mut(index)
+    }
+}
+
+impl<T: fmt::Write> fmt::Write for MaybeOwnedMut<'_, T> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.deref_mut().write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.deref_mut().write_char(c)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: io::Write> io::Write for MaybeOwnedMut<'_, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.deref_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.deref_mut().flush()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: io::Read> io::Read for MaybeOwnedMut<'_, T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.deref_mut().read(buf)
+    }
+}
+
+/// Borrowed data is cloned before the first write.
+impl<T: fmt::Write + Clone> fmt::Write for MaybeOwned<'_, T> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.make_owned().write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.make_owned().write_char(c)
+    }
+}
+
 impl<T> AsMut<T> for MaybeOwnedMut<'_, T> {
     fn as_mut(&mut self) -> &mut T {
         match self {
@@ -407,22 +1684,102 @@ impl<T> BorrowMut<T> for MaybeOwnedMut<'_, T> {
     }
 }
 
+impl<'a, T> MaybeOwnedMut<'a, T> {
+    /// Converts it into a `MaybeOwned`, moving owned data and reborrowing
+    /// borrowed data immutably.
+    ///
+    /// The same conversion is available through `From`/`Into`.
+    pub fn into_immutable(self) -> MaybeOwned<'a, T> {
+        match self {
+            Self::Owned(v) => MaybeOwned::Owned(v),
+            Self::Borrowed(v) => MaybeOwned::Borrowed(v),
+        }
+    }
+}
+
 impl<T> MaybeOwnedMut<'_, T> {
     /// Returns a new `MaybeOwnedMut::Borrowed` without cloning the data.
+    ///
+    /// This reborrows it with a shorter lifetime, e.g. to pass it to a
+    /// function expecting a `MaybeOwnedMut` without giving up owned data.
+    /// Changes made through the result are visible in `self`.
     pub fn to_mut(&mut self) -> MaybeOwnedMut<'_, T> {
         match self {
             Self::Owned(v) => MaybeOwnedMut::Borrowed(v),
             Self::Borrowed(v) => MaybeOwnedMut::Borrowed(v),
         }
     }
+
+    /// Replaces the data with `value`, returning the previous data.
+    ///
+    /// Like `mem::replace`, for the borrowed variant the source is
+    /// updated in place instead of converting it to the owned variant.
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(self.deref_mut(), value)
+    }
+
+    /// Swaps the data with the data of `other`, like `mem::swap`.
+    ///
+    /// For borrowed variants the sources are updated in place. This is
+    /// not named `swap` so it doesn't shadow e.g. `<[T]>::swap` reached
+    /// through `DerefMut`.
+    pub fn swap_with(&mut self, other: &mut MaybeOwnedMut<'_, T>) {
+        mem::swap(self.deref_mut(), other.deref_mut())
+    }
+
+    /// Takes the data, leaving `T::default()` in it's place.
+    ///
+    /// Like `mem::take`, for the borrowed variant the source is
+    /// updated in place instead of converting it to the owned variant.
+    pub fn take(&mut self) -> T
+    where
+        T: Default,
+    {
+        mem::take(self.deref_mut())
+    }
+
+    /// Mutably borrows two disjoint parts of the data at once.
+    ///
+    /// `f` is called with a `&mut T` and has to do the actual split, e.g.
+    /// `|v| (&mut v.a, &mut v.b)`. For the borrowed variant this mutates
+    /// the source in place.
+    pub fn split_fields<A, B, F>(&mut self, f: F) -> (&mut A, &mut B)
+    where
+        F: FnOnce(&mut T) -> (&mut A, &mut B),
+    {
+        f(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> MaybeOwnedMut<'_, Vec<T>> {
+    /// Returns the contained `Vec` as mutable slice.
+    ///
+    /// For the borrowed variant this mutates the source in place.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
+    use std::cell::Cell;
+    use std::string::ToString;
 
     type TestType = Vec<()>;
 
+    /// Counts how often it was cloned in the referenced `Cell`.
+    struct CloneCounter<'c>(&'c Cell<usize>);
+
+    impl Clone for CloneCounter<'_> {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            CloneCounter(self.0)
+        }
+    }
+
     fn with_into<'a, I: Into<MaybeOwned<'a, TestType>>>(v: I) -> MaybeOwned<'a, TestType> {
         v.into()
     }
@@ -431,6 +1788,43 @@ mod tests {
     fn is_owned() {
         let data = TestType::default();
         assert!(MaybeOwned::Owned(data).is_owned());
+        assert!(!MaybeOwned::Owned(TestType::default()).is_borrowed());
+
+        let data = TestType::default();
+        assert!(MaybeOwned::Borrowed(&data).is_borrowed());
+        assert!(!MaybeOwned::Borrowed(&data).is_owned());
+
+        let mut data = TestType::default();
+        assert!(MaybeOwnedMut::Borrowed(&mut data).is_borrowed());
+        assert!(!MaybeOwnedMut::Owned(TestType::default()).is_borrowed());
+    }
+
+    #[test]
+    fn copied() {
+        let data = 12u32;
+        assert_eq!(MaybeOwned::Borrowed(&data).copied(), 12);
+        assert_eq!(MaybeOwned::Owned(13u32).copied(), 13);
+
+        let mut data = 14u32;
+        assert_eq!(MaybeOwnedMut::Borrowed(&mut data).copied(), 14);
+        assert_eq!(MaybeOwnedMut::Owned(15u32).copied(), 15);
+    }
+
+    #[test]
+    fn const_constructors() {
+        static DATA: u8 = 12;
+        const BORROWED: MaybeOwned<'static, u8> = MaybeOwned::borrowed(&DATA);
+        static OWNED: MaybeOwned<'static, u8> = MaybeOwned::owned(13);
+
+        assert!(BORROWED.is_borrowed());
+        assert_eq!(BORROWED, 12);
+        assert!(OWNED.is_owned());
+        assert_eq!(OWNED, 13);
+
+        let mut data = 14u8;
+        let maybe = MaybeOwnedMut::borrowed(&mut data);
+        assert!(maybe.is_borrowed());
+        assert!(MaybeOwnedMut::owned(15u8).is_owned());
     }
 
     #[test]
@@ -478,14 +1872,25 @@ mod tests {
     #[test]
     fn has_default() {
         #[derive(Default)]
-        struct TestType(u8);
+        struct TestType(#[allow(dead_code)] u8);
         let _x: MaybeOwned<TestType> = Default::default();
     }
 
+    #[test]
+    fn from_iterator() {
+        let maybe: MaybeOwned<Vec<u32>> = (0..5).collect();
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, vec![0, 1, 2, 3, 4]);
+
+        let maybe: MaybeOwnedMut<String> = "abc".chars().rev().collect();
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, "cba");
+    }
+
     #[test]
     fn has_clone() {
         #[derive(Clone)]
-        struct TestType(u8);
+        struct TestType(#[allow(dead_code)] u8);
         let _x = TestType(12).clone();
     }
 
@@ -504,6 +1909,35 @@ mod tests {
         a[0] = 12u8;
     }
 
+    #[test]
+    fn has_index() {
+        fn first<I: Index<usize, Output = u8>>(v: &I) -> u8 {
+            v[0]
+        }
+
+        let data = vec![1u8, 2, 3];
+        let a = MaybeOwned::Borrowed(&data);
+        assert_eq!(first(&a), 1);
+        assert_eq!(a[1..], [2, 3]);
+
+        let mut data = vec![1u8, 2, 3];
+        let b = MaybeOwnedMut::Borrowed(&mut data);
+        assert_eq!(first(&b), 1);
+    }
+
+    #[test]
+    fn has_index_mut() {
+        fn set_first<I: IndexMut<usize, Output = u8>>(v: &mut I, value: u8) {
+            v[0] = value;
+        }
+
+        let mut data = vec![1u8, 2, 3];
+        let mut a = MaybeOwnedMut::Borrowed(&mut data);
+        set_first(&mut a, 12);
+        a[1] = 13;
+        assert_eq!(data, vec![12, 13, 3]);
+    }
+
     #[test]
     fn has_partial_eq() {
         #[derive(PartialEq)]
@@ -564,6 +1998,22 @@ mod tests {
         assert_eq!(a < c, false);
     }
 
+    #[test]
+    fn cross_type_cmp() {
+        let mut five = 5;
+        let a = MaybeOwned::Owned(5);
+        let b = MaybeOwnedMut::Borrowed(&mut five);
+        assert!(a == b);
+        assert!(b == a);
+        assert!(a <= b);
+
+        let c = MaybeOwnedMut::Owned(7);
+        assert!(a != c);
+        assert!(a < c);
+        assert!(c > a);
+        assert_eq!(c.partial_cmp(&a), Some(Ordering::Greater));
+    }
+
     #[test]
     fn has_hash() {
         use std::collections::HashMap;
@@ -575,8 +2025,47 @@ mod tests {
     }
 
     #[test]
-    fn has_borrow() {
-        let v = MaybeOwned::Owned(42);
+    fn hash_matches_bare_value() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        fn hash_of<H: Hash + ?Sized>(value: &H) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        for value in [0u64, 1, 42, u64::MAX] {
+            let bare = hash_of(&value);
+            assert_eq!(hash_of(&MaybeOwned::Owned(value)), bare);
+            assert_eq!(hash_of(&MaybeOwned::Borrowed(&value)), bare);
+            assert_eq!(hash_of(&MaybeOwnedMut::Owned(value)), bare);
+            let mut copy = value;
+            assert_eq!(hash_of(&MaybeOwnedMut::Borrowed(&mut copy)), bare);
+        }
+
+        for value in ["", "a", "abc"] {
+            let bare = hash_of(value);
+            assert_eq!(
+                hash_of(&MaybeOwned::Owned(value.to_owned())),
+                hash_of(&value.to_owned())
+            );
+            assert_eq!(hash_of(&MaybeOwnedStr::Owned(value.to_owned())), bare);
+            assert_eq!(hash_of(&MaybeOwnedStr::Borrowed(value)), bare);
+        }
+
+        // lookups through `Borrow<T>` rely on the equal hashes
+        let mut map = HashMap::new();
+        map.insert(MaybeOwned::Owned(42u64), 33);
+        assert_eq!(map.get(&42), Some(&33));
+        let mut map = HashMap::new();
+        map.insert(MaybeOwnedStr::Borrowed("key"), 33);
+        assert_eq!(map.get("key"), Some(&33));
+    }
+
+    #[test]
+    fn has_borrow() {
+        let v = MaybeOwned::Owned(42);
         let _ = Borrow::<u8>::borrow(&v);
 
         let v = MaybeOwnedMut::Owned(42);
@@ -612,6 +2101,34 @@ mod tests {
         let _: &mut u8 = v.as_mut();
     }
 
+    #[test]
+    fn owned_ref_and_as_owned_mut() {
+        let clones = Cell::new(0);
+
+        let data = CloneCounter(&clones);
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        assert!(maybe.owned_ref().is_none());
+        assert!(maybe.as_owned_mut().is_none());
+        assert!(maybe.is_borrowed());
+
+        let mut data = CloneCounter(&clones);
+        let mut maybe = MaybeOwnedMut::Borrowed(&mut data);
+        assert!(maybe.owned_ref().is_none());
+        assert!(maybe.as_owned_mut().is_none());
+        assert!(maybe.is_borrowed());
+        assert_eq!(clones.get(), 0);
+
+        let mut maybe = MaybeOwned::Owned(12u8);
+        assert_eq!(maybe.owned_ref(), Some(&12));
+        *maybe.as_owned_mut().unwrap() += 1;
+        assert_eq!(maybe, 13);
+
+        let mut maybe = MaybeOwnedMut::Owned(12u8);
+        assert_eq!(maybe.owned_ref(), Some(&12));
+        *maybe.as_owned_mut().unwrap() += 1;
+        assert_eq!(maybe, 13);
+    }
+
     #[test]
     fn has_display() {
         let n = 33;
@@ -623,6 +2140,84 @@ mod tests {
         assert_eq!(s, "42 33");
     }
 
+    #[test]
+    fn display_for_each_variant() {
+        for_each_variant(42, |v| assert_eq!(format!("{}", v), "42"));
+    }
+
+    #[test]
+    fn has_fmt_write() {
+        use std::fmt::Write;
+
+        let mut data = String::from("a");
+        let mut maybe = MaybeOwnedMut::Borrowed(&mut data);
+        write!(maybe, "b{}", 1).unwrap();
+        maybe.write_char('c').unwrap();
+        assert!(maybe.is_borrowed());
+        assert_eq!(data, "ab1c");
+
+        let data = String::from("a");
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        write!(maybe, "b{}", 1).unwrap();
+        maybe.write_char('c').unwrap();
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, "ab1c");
+        assert_eq!(data, "a");
+    }
+
+    #[test]
+    fn has_io_write_and_read() {
+        use std::io::{Read, Write};
+
+        let mut buf = vec![1u8];
+        let mut maybe = MaybeOwnedMut::Borrowed(&mut buf);
+        maybe.write_all(&[2, 3]).unwrap();
+        maybe.flush().unwrap();
+        assert_eq!(buf, vec![1, 2, 3]);
+
+        let mut input = &b"abc"[..];
+        let mut maybe = MaybeOwnedMut::Borrowed(&mut input);
+        let mut out = [0u8; 2];
+        maybe.read_exact(&mut out).unwrap();
+        assert_eq!(&out, b"ab");
+        assert_eq!(input, b"c");
+    }
+
+    #[test]
+    fn has_error() {
+        use std::io;
+
+        #[derive(Debug)]
+        struct Outer(io::Error);
+
+        impl fmt::Display for Outer {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("outer")
+            }
+        }
+
+        impl Error for Outer {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        fn describe(e: &dyn Error) -> String {
+            e.to_string()
+        }
+
+        let err = MaybeOwned::Owned(io::Error::other("inner"));
+        assert_eq!(describe(&err), "inner");
+
+        let outer = Outer(io::Error::other("inner"));
+        let err = MaybeOwned::Borrowed(&outer);
+        assert_eq!(describe(&err), "outer");
+        assert_eq!(err.source().unwrap().to_string(), "inner");
+
+        let boxed: Box<dyn Error> = Box::new(MaybeOwned::Owned(Outer(io::Error::other("inner"))));
+        assert_eq!(boxed.source().unwrap().to_string(), "inner");
+    }
+
     #[test]
     fn from_cow() {
         use std::borrow::Cow;
@@ -649,12 +2244,32 @@ mod tests {
         test(MaybeOwned::Borrowed(&n), n);
     }
 
+    #[test]
+    fn mut_into_cow() {
+        use std::borrow::Cow;
+
+        let cow = Cow::from(MaybeOwnedMut::Owned("a".to_owned()));
+        assert!(matches!(cow, Cow::Owned(ref v) if v == "a"));
+
+        let mut data = "b".to_owned();
+        let mut maybe = MaybeOwnedMut::Borrowed(&mut data);
+        maybe.push('c');
+        let mut cow = Cow::from(maybe);
+        assert!(matches!(cow, Cow::Owned(ref v) if v == "bc"));
+        cow.to_mut().push('d');
+        assert_eq!(*cow, "bcd");
+        assert_eq!(data, "bc");
+    }
+
     #[test]
     fn from_str() {
         let as_string = "12";
         //assumption as_string is convertable to u32
-        assert_eq!(12u32, as_string.parse().unwrap());
-        assert_eq!(MaybeOwned::Owned(12u32), as_string.parse().unwrap());
+        assert_eq!(12u32, as_string.parse::<u32>().unwrap());
+        assert_eq!(
+            MaybeOwned::Owned(12u32),
+            as_string.parse::<MaybeOwned<u32>>().unwrap()
+        );
     }
 
     #[test]
@@ -684,6 +2299,76 @@ mod tests {
         assert_eq!(&[0, 1], &value[..]);
     }
 
+    #[test]
+    fn to_mut_reborrows() {
+        fn push(mut v: MaybeOwnedMut<Vec<u32>>) {
+            v.push(1);
+        }
+
+        let mut value = MaybeOwnedMut::Owned(vec![0u32]);
+        push(value.to_mut());
+        assert!(value.is_owned());
+        assert_eq!(&[0, 1], &value[..]);
+
+        let mut data = vec![0u32];
+        let mut value = MaybeOwnedMut::Borrowed(&mut data);
+        push(value.to_mut());
+        push(value.to_mut());
+        assert_eq!(data, vec![0, 1, 1]);
+    }
+
+    #[test]
+    fn mut_replace_and_take() {
+        let mut data = vec![1u8];
+        let mut maybe = MaybeOwnedMut::Borrowed(&mut data);
+        assert_eq!(maybe.replace(vec![2]), vec![1]);
+        assert_eq!(maybe.take(), vec![2]);
+        assert!(maybe.is_borrowed());
+        assert_eq!(data, Vec::<u8>::new());
+
+        let mut maybe = MaybeOwnedMut::Owned(vec![1u8]);
+        assert_eq!(maybe.replace(vec![2]), vec![1]);
+        assert_eq!(maybe.take(), vec![2]);
+        assert!(maybe.is_owned());
+        assert!(maybe.is_empty());
+    }
+
+    #[test]
+    fn mut_swap_with() {
+        let mut left = vec![1u8];
+        let mut right = vec![2u8, 3];
+        let mut a = MaybeOwnedMut::Borrowed(&mut left);
+        let mut b = MaybeOwnedMut::Borrowed(&mut right);
+        a.swap_with(&mut b);
+        assert!(a.is_borrowed() && b.is_borrowed());
+        assert_eq!(left, vec![2, 3]);
+        assert_eq!(right, vec![1]);
+
+        let mut left = vec![1u8];
+        let mut a = MaybeOwnedMut::Borrowed(&mut left);
+        let mut b = MaybeOwnedMut::Owned(vec![2u8]);
+        a.swap_with(&mut b);
+        assert!(b.is_owned());
+        assert_eq!(*b, vec![1]);
+        assert_eq!(left, vec![2]);
+    }
+
+    #[test]
+    fn maybe_owned_common() {
+        fn owned_len<M: MaybeOwnedCommon<Vec<u8>>>(value: M) -> (bool, usize) {
+            let len = value.get_ref().len();
+            (value.is_owned(), value.into_owned().len() + len)
+        }
+
+        let data = vec![1u8];
+        assert_eq!(owned_len(MaybeOwned::Borrowed(&data)), (false, 2));
+        assert_eq!(owned_len(MaybeOwned::Owned(vec![1, 2])), (true, 4));
+
+        let mut data = vec![1u8];
+        assert_eq!(owned_len(MaybeOwnedMut::Borrowed(&mut data)), (false, 2));
+        assert_eq!(owned_len(MaybeOwnedMut::Owned(vec![1, 2])), (true, 4));
+    }
+
     #[test]
     fn to_ref_borrowed() {
         let data = TestType::default();
@@ -717,4 +2402,1349 @@ mod tests {
         assert_eq!(cloned.deref_mut(), &mut TestType::default());
         assert!(!cloned.is_owned());
     }
+
+    #[test]
+    fn map_borrowed() {
+        let data = [1u8, 2];
+        let maybe = MaybeOwned::Borrowed(&data[0]).map_borrowed(|_| &data[1]);
+        assert!(!maybe.is_owned());
+        assert_eq!(*maybe, 2);
+
+        let maybe = MaybeOwned::Owned(1u8).map_borrowed(|_| &data[1]);
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, 1);
+    }
+
+    #[test]
+    fn map_borrowed_mut() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Node {
+            value: u8,
+            child: Option<Box<Node>>,
+        }
+
+        let mut data = Node {
+            value: 1,
+            child: Some(Box::new(Node {
+                value: 2,
+                child: None,
+            })),
+        };
+        let child_ptr: *const Node = &**data.child.as_ref().unwrap();
+        {
+            let mut maybe = MaybeOwnedMut::Borrowed(&mut data)
+                .map_borrowed(|v| v.child.as_deref_mut().unwrap());
+            assert!(std::ptr::eq(&*maybe, child_ptr));
+            maybe.value = 3;
+        }
+        assert_eq!(data.value, 1);
+        assert_eq!(data.child.as_ref().unwrap().value, 3);
+
+        let maybe =
+            MaybeOwnedMut::Owned(data.clone()).map_borrowed(|v| v.child.as_deref_mut().unwrap());
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, data);
+    }
+
+    #[test]
+    fn into_owned_with() {
+        #[derive(Debug, PartialEq)]
+        struct Handle(u32);
+
+        let data = Handle(12);
+        let maybe = MaybeOwned::Borrowed(&data);
+        assert_eq!(maybe.into_owned_with(|h| Handle(h.0 + 1)), Handle(13));
+
+        let maybe: MaybeOwned<Handle> = Handle(3).into();
+        assert_eq!(maybe.into_owned_with(|_| unreachable!()), Handle(3));
+    }
+
+    #[test]
+    fn make_owned_with() {
+        #[derive(Debug, PartialEq)]
+        struct Handle(u32);
+
+        let mut data = Handle(12);
+        {
+            let mut maybe = MaybeOwnedMut::Borrowed(&mut data);
+            let owned = maybe.make_owned_with(|h| Handle(h.0));
+            owned.0 = 42;
+            assert!(maybe.is_owned());
+            assert_eq!(*maybe, Handle(42));
+        }
+        assert_eq!(data, Handle(12));
+
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        assert_eq!(maybe.make_owned_with(|h| Handle(h.0 + 1)), &mut Handle(13));
+        assert!(maybe.is_owned());
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
+    fn from_smart_pointer_refs() {
+        fn test<'a, V: Into<MaybeOwned<'a, u8>>>(v: V) {
+            let maybe = v.into();
+            assert!(!maybe.is_owned());
+            assert_eq!(*maybe, 12);
+        }
+
+        test(&Box::new(12u8));
+        test(&Rc::new(12u8));
+        test(&Arc::new(12u8));
+    }
+
+    #[test]
+    fn from_box() {
+        #[derive(Debug, PartialEq)]
+        struct PseudoBigData(u8);
+
+        fn test<'a, V: Into<MaybeOwned<'a, PseudoBigData>>>(v: V) {
+            let maybe = v.into();
+            assert!(maybe.is_owned());
+            assert_eq!(*maybe, PseudoBigData(1));
+        }
+
+        test(Box::new(PseudoBigData(1)));
+    }
+
+    #[test]
+    fn from_mut_ref() {
+        fn test<'a, V: Into<MaybeOwned<'a, u8>>>(v: V) -> MaybeOwned<'a, u8> {
+            v.into()
+        }
+
+        let mut data = 12u8;
+        let data_ref = &mut data;
+        *data_ref += 1;
+        let maybe = test(data_ref);
+        assert!(maybe.is_borrowed());
+        assert_eq!(*maybe, 13);
+    }
+
+    #[test]
+    fn into_value() {
+        let data = 12u8;
+        let value: u32 = MaybeOwned::Borrowed(&data).into_value();
+        assert_eq!(value, 12);
+
+        let value: u32 = MaybeOwned::Owned(13u8).into_value();
+        assert_eq!(value, 13);
+    }
+
+    #[test]
+    fn zip_with() {
+        let a = 2u8;
+        let b = String::from("ab");
+        let len = |l: &u8, r: &String| *l as usize + r.len();
+
+        let combined = MaybeOwned::Owned(2u8).zip_with(MaybeOwned::Owned(b.clone()), len);
+        assert_eq!(combined, MaybeOwned::Owned(4));
+
+        let combined = MaybeOwned::Owned(2u8).zip_with(MaybeOwned::Borrowed(&b), len);
+        assert_eq!(combined, MaybeOwned::Owned(4));
+
+        let combined = MaybeOwned::Borrowed(&a).zip_with(MaybeOwned::Owned(b.clone()), len);
+        assert_eq!(combined, MaybeOwned::Owned(4));
+
+        let combined = MaybeOwned::Borrowed(&a).zip_with(MaybeOwned::Borrowed(&b), len);
+        assert!(combined.is_owned());
+        assert_eq!(*combined, 4);
+    }
+
+    #[test]
+    fn try_into_borrowed() {
+        let data = 12u8;
+        let maybe = MaybeOwned::Borrowed(&data);
+        assert_eq!(maybe.try_into_borrowed(), Ok(&12));
+
+        let maybe: MaybeOwned<u8> = MaybeOwned::Owned(13);
+        assert_eq!(maybe.try_into_borrowed(), Err(13));
+
+        let mut data = 12u8;
+        let maybe = MaybeOwnedMut::Borrowed(&mut data);
+        *maybe.try_into_borrowed().unwrap() = 14;
+        assert_eq!(data, 14);
+    }
+
+    #[test]
+    fn try_into_owned() {
+        #[derive(Debug, PartialEq)]
+        struct NotClone(u8);
+
+        let maybe = MaybeOwned::Owned(NotClone(13));
+        assert_eq!(maybe.try_into_owned(), Ok(NotClone(13)));
+
+        let data = NotClone(12);
+        let maybe = MaybeOwned::Borrowed(&data);
+        assert_eq!(maybe.try_into_owned(), Err(&NotClone(12)));
+
+        let mut data = NotClone(12);
+        let maybe = MaybeOwnedMut::Borrowed(&mut data);
+        maybe.try_into_owned().unwrap_err().0 = 14;
+        assert_eq!(data, NotClone(14));
+    }
+
+    #[test]
+    fn promote() {
+        let data = vec![1u8];
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        let first = maybe.promote() as *const _;
+        assert!(maybe.is_owned());
+        assert_ne!(first, &data as *const _);
+
+        let second = maybe.promote() as *const _;
+        assert_eq!(first, second);
+        assert_eq!(*maybe, data);
+    }
+
+    #[test]
+    fn make_owned_is_idempotent() {
+        let clones = Cell::new(0);
+        let data = CloneCounter(&clones);
+        let mut maybe = MaybeOwned::Borrowed(&data);
+
+        let first = maybe.make_owned() as *const _;
+        assert_eq!(clones.get(), 1);
+        let second = maybe.make_owned() as *const _;
+        assert_eq!(clones.get(), 1);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn peek_mut() {
+        let data = vec![1u8, 2];
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        {
+            let peek = maybe.peek_mut();
+            assert_eq!(peek.len(), 2);
+            assert!(!peek.is_owned());
+        }
+        assert!(!maybe.is_owned());
+
+        {
+            let mut peek = maybe.peek_mut();
+            if peek.len() == 2 {
+                peek.to_mut().push(3);
+            }
+            assert!(peek.is_owned());
+        }
+        assert_eq!(*maybe, vec![1, 2, 3]);
+        assert_eq!(data, vec![1, 2]);
+
+        maybe.peek_mut().into_mut().push(4);
+        assert_eq!(*maybe, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn split_fields() {
+        struct Pair {
+            a: Vec<u8>,
+            b: u8,
+        }
+
+        let mut data = Pair { a: vec![], b: 0 };
+        {
+            let mut maybe = MaybeOwnedMut::Borrowed(&mut data);
+            let (a, b) = maybe.split_fields(|v| (&mut v.a, &mut v.b));
+            a.push(*b);
+            *b = 12;
+        }
+        assert_eq!(data.a, vec![0]);
+        assert_eq!(data.b, 12);
+    }
+
+    #[test]
+    fn into_immutable() {
+        let mut data = 12u8;
+        let ptr: *const u8 = &data;
+        let maybe = MaybeOwnedMut::Borrowed(&mut data).into_immutable();
+        assert!(!maybe.is_owned());
+        assert!(std::ptr::eq(&*maybe, ptr));
+
+        let maybe: MaybeOwned<u8> = MaybeOwnedMut::Owned(13u8).into();
+        assert_eq!(maybe, MaybeOwned::Owned(13));
+    }
+
+    #[test]
+    fn display_annotated() {
+        let n = 33;
+        let a = MaybeOwned::Owned(42);
+        let b = MaybeOwned::Borrowed(&n);
+
+        assert_eq!(a.display_annotated().to_string(), "42 (owned)");
+        assert_eq!(b.display_annotated().to_string(), "33 (borrowed)");
+
+        let mut n = 12;
+        let c = MaybeOwnedMut::Borrowed(&mut n);
+        assert_eq!(c.display_annotated().to_string(), "12 (borrowed)");
+    }
+
+    #[test]
+    fn debug() {
+        let n = 33;
+        let a = MaybeOwned::Owned(42);
+        let b = MaybeOwned::Borrowed(&n);
+
+        assert_eq!(format!("{:?}", a.debug()), "42");
+        assert_eq!(format!("{:?}", b.debug()), "33");
+        assert_eq!(format!("{:#?}", a.debug()), "Owned(\n    42,\n)");
+        assert_eq!(format!("{:#?}", b.debug()), "Borrowed(\n    33,\n)");
+
+        let mut n = 12;
+        let c = MaybeOwnedMut::Borrowed(&mut n);
+        assert_eq!(format!("{:?}", c.debug()), "12");
+        assert_eq!(format!("{:#?}", c.debug()), "Borrowed(\n    12,\n)");
+    }
+
+    #[test]
+    fn clone_from_reuses_owned() {
+        let mut target: MaybeOwned<Vec<u8>> = MaybeOwned::Owned(Vec::with_capacity(32));
+        target.clone_from(&MaybeOwned::Owned(vec![1, 2]));
+        assert_eq!(*target, vec![1, 2]);
+        assert!(target.capacity() >= 32);
+
+        let data = vec![3u8];
+        target.clone_from(&MaybeOwned::Borrowed(&data));
+        assert!(!target.is_owned());
+        assert_eq!(*target, vec![3]);
+
+        target.clone_from(&MaybeOwned::Owned(vec![4]));
+        assert!(target.is_owned());
+        assert_eq!(*target, vec![4]);
+    }
+
+    #[test]
+    fn string_into_cow() {
+        let data = String::from("abc");
+        let cow = MaybeOwned::Borrowed(&data).into_cow();
+        assert!(matches!(cow, Cow::Borrowed("abc")));
+
+        let cow = MaybeOwned::Owned(String::from("def")).into_cow();
+        assert!(matches!(cow, Cow::Owned(ref v) if v == "def"));
+    }
+
+    #[test]
+    fn vec_into_cow() {
+        let data = vec![1u8, 2];
+        let cow = MaybeOwned::Borrowed(&data).into_cow();
+        assert!(matches!(cow, Cow::Borrowed(&[1, 2])));
+
+        let cow = MaybeOwned::Owned(vec![3u8]).into_cow();
+        assert!(matches!(cow, Cow::Owned(ref v) if v == &[3]));
+    }
+
+    #[test]
+    fn as_hash_map_key_with_entry_api() {
+        use std::collections::HashMap;
+
+        let shared = String::from("shared");
+        let mut map: HashMap<MaybeOwned<String>, u32> = HashMap::new();
+
+        // the entry API takes the key by value, a borrowed key is stored
+        // as borrowed key and not cloned
+        *map.entry(MaybeOwned::Borrowed(&shared)).or_insert(0) += 1;
+        *map.entry(MaybeOwned::Owned(String::from("shared")))
+            .or_insert(0) += 1;
+        *map.entry(MaybeOwned::Owned(String::from("owned")))
+            .or_insert(0) += 1;
+        assert!(!map
+            .keys()
+            .find(|k| k.as_str() == "shared")
+            .unwrap()
+            .is_owned());
+
+        assert_eq!(map.get(&MaybeOwned::Borrowed(&shared)), Some(&2));
+        assert_eq!(map.get(&MaybeOwned::Owned(String::from("owned"))), Some(&1));
+        // lookup through `Borrow<String>`
+        assert_eq!(map.get(&shared), Some(&2));
+
+        *map.get_mut(&String::from("owned")).unwrap() += 1;
+        *map.get_mut(&MaybeOwned::Borrowed(&shared)).unwrap() += 1;
+        assert_eq!(map[&String::from("owned")], 2);
+        assert_eq!(map[&shared], 3);
+    }
+
+    #[test]
+    fn clone_into_or_take() {
+        let data = vec![1u8, 2, 3];
+        let mut buf = None;
+
+        MaybeOwned::Borrowed(&data).clone_into_or_take(&mut buf);
+        let first = buf.as_ref().unwrap().as_ptr();
+        let cloned = MaybeOwned::Borrowed(&data).clone_into_or_take(&mut buf);
+        assert_eq!(cloned.as_ptr(), first);
+        assert_eq!(buf, Some(vec![1, 2, 3]));
+
+        let taken = MaybeOwned::Owned(vec![4u8]).clone_into_or_take(&mut buf);
+        assert_eq!(taken, &mut vec![4]);
+    }
+
+    #[test]
+    fn cmp_by() {
+        let data = (2u8, "b");
+        let mut items = [
+            MaybeOwned::Owned((3u8, "a")),
+            MaybeOwned::Borrowed(&data),
+            MaybeOwned::Owned((1u8, "c")),
+        ];
+
+        items.sort_by(|l, r| l.cmp_by(r, |l, r| l.1.cmp(r.1)));
+        let order: Vec<u8> = items.iter().map(|v| v.0).collect();
+        assert_eq!(order, vec![3, 2, 1]);
+
+        items.sort_by(|l, r| l.cmp_by(r, |l, r| l.0.cmp(&r.0)));
+        let order: Vec<u8> = items.iter().map(|v| v.0).collect();
+        assert_eq!(order, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn ptr_hash_uses_identity_for_borrowed() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash_of<T: Hash>(v: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            v.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = String::from("abc");
+        let b = String::from("abc");
+        let a1 = MaybeOwnedPtrHash(MaybeOwned::Borrowed(&a));
+        let a2 = MaybeOwnedPtrHash(MaybeOwned::Borrowed(&a));
+        let b1 = MaybeOwnedPtrHash(MaybeOwned::Borrowed(&b));
+
+        assert_eq!(hash_of(&a1), hash_of(&a2));
+        assert!(a1 == a2);
+        assert!(a1 != b1);
+
+        let o1 = MaybeOwnedPtrHash(MaybeOwned::Owned(String::from("abc")));
+        let o2 = MaybeOwnedPtrHash(MaybeOwned::Owned(String::from("abc")));
+        assert_eq!(hash_of(&o1), hash_of(&o2));
+        assert!(o1 == o2);
+        assert!(o1 != a1);
+    }
+
+    #[test]
+    fn validate() {
+        fn non_zero(v: &u8) -> Result<(), &'static str> {
+            if *v == 0 {
+                Err("zero")
+            } else {
+                Ok(())
+            }
+        }
+
+        let data = 1u8;
+        let maybe = MaybeOwned::Borrowed(&data).validate(non_zero).unwrap();
+        assert!(!maybe.is_owned());
+
+        let maybe = MaybeOwnedMut::Owned(0u8).validate(non_zero);
+        assert_eq!(maybe.unwrap_err(), "zero");
+    }
+
+    #[test]
+    fn access() {
+        let data = 12u8;
+        let long = {
+            let maybe = MaybeOwned::Borrowed(&data);
+            maybe.access().long()
+        };
+        assert_eq!(long, Some(&12));
+
+        let maybe = MaybeOwned::Owned(13u8);
+        let access = maybe.access();
+        assert_eq!(access, Ref::Short(&13));
+        assert_eq!(*access, 13);
+        assert_eq!(access.long(), None);
+    }
+
+    #[test]
+    fn borrow_or_default() {
+        let data = 12u8;
+        let maybe = MaybeOwned::borrow_or_default(Some(&data));
+        assert!(!maybe.is_owned());
+        assert_eq!(*maybe, 12);
+
+        let maybe = MaybeOwned::<u8>::borrow_or_default(None);
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, 0);
+    }
+
+    #[test]
+    fn from_fn() {
+        let maybe = MaybeOwned::from_fn(|| vec![1u8]);
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, vec![1]);
+    }
+
+    #[test]
+    fn borrow_or_else() {
+        let calls = Cell::new(0);
+        let make = || {
+            calls.set(calls.get() + 1);
+            13u8
+        };
+
+        let data = 12u8;
+        let maybe = MaybeOwned::borrow_or_else(Some(&data), make);
+        assert!(!maybe.is_owned());
+        assert_eq!(calls.get(), 0);
+
+        let maybe = MaybeOwned::borrow_or_else(None, make);
+        assert_eq!(maybe, MaybeOwned::Owned(13));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn merge() {
+        use std::collections::HashMap;
+
+        let mut base = HashMap::new();
+        base.insert("a", 1);
+        base.insert("b", 2);
+        let mut layer = HashMap::new();
+        layer.insert("b", 3);
+        layer.insert("c", 4);
+
+        let merged = MaybeOwned::Borrowed(&base).merge(MaybeOwned::Owned(layer));
+        assert!(merged.is_owned());
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged["a"], 1);
+        assert_eq!(merged["b"], 3);
+        assert_eq!(merged["c"], 4);
+        assert_eq!(base["b"], 2);
+    }
+
+    #[test]
+    fn into_shared() {
+        let data = vec![1u8];
+        let shared = MaybeOwned::Borrowed(&data).into_shared();
+        assert_eq!(*shared, data);
+    }
+
+    #[test]
+    fn intern() {
+        let pool = Mutex::new(HashMap::new());
+        let data = String::from("abc");
+
+        let first = MaybeOwned::Borrowed(&data).intern(&pool);
+        let second = MaybeOwned::Owned(String::from("abc")).intern(&pool);
+        let other = MaybeOwned::Owned(String::from("def")).intern(&pool);
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(pool.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn sub() {
+        let data = vec![1u8, 2, 3, 4];
+        let maybe = MaybeOwned::Borrowed(&data);
+        assert_eq!(maybe.sub(1..3), &[2, 3]);
+        assert_eq!(maybe.sub(1..3).as_ptr(), data[1..].as_ptr());
+
+        let maybe = MaybeOwnedMut::Owned(vec![1u8, 2, 3, 4]);
+        assert_eq!(maybe.sub(0..1), &[1]);
+    }
+
+    #[test]
+    fn as_mut_slice() {
+        let mut data = vec![1u8, 2];
+        {
+            let mut maybe = MaybeOwnedMut::Borrowed(&mut data);
+            maybe.as_mut_slice()[1] = 3;
+        }
+        assert_eq!(data, vec![1, 3]);
+    }
+
+    #[test]
+    fn project_opt() {
+        let mut data = HashMap::new();
+        data.insert("a", vec![1u8]);
+        let maybe = MaybeOwned::Borrowed(&data);
+
+        let projected = maybe.project_opt(|m| m.get("a")).unwrap();
+        assert!(!projected.is_owned());
+        assert_eq!(*projected, vec![1]);
+        assert!(maybe.project_opt(|m| m.get("b")).is_none());
+    }
+
+    #[test]
+    fn as_maybe_owned() {
+        fn len<A: AsMaybeOwned<TestType>>(v: &A) -> usize {
+            let maybe = v.as_maybe_owned();
+            assert!(!maybe.is_owned());
+            maybe.len()
+        }
+
+        let data = vec![(); 2];
+        assert_eq!(len(&data), 2);
+        assert_eq!(len(&&data), 2);
+        assert_eq!(len(&MaybeOwned::Owned(data.clone())), 2);
+        assert_eq!(len(&Cow::Borrowed(&data)), 2);
+    }
+
+    #[test]
+    fn owned_or_default() {
+        let data = vec![1u8];
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        assert_eq!(maybe.owned_or_default(), &mut Vec::<u8>::new());
+        assert!(maybe.is_owned());
+
+        let mut maybe: MaybeOwned<Vec<u8>> = MaybeOwned::Owned(vec![2]);
+        maybe.owned_or_default().push(3);
+        assert_eq!(*maybe, vec![2, 3]);
+    }
+
+    #[test]
+    fn no_clone_owned() {
+        let mut maybe = NoClone::new(MaybeOwned::Owned(vec![1u8]));
+        maybe.make_owned().push(2);
+        assert_eq!(maybe.into_owned(), vec![1, 2]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn no_clone_into_owned_borrowed() {
+        let data = vec![1u8];
+        let _ = NoClone::new(MaybeOwned::Borrowed(&data)).into_owned();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn no_clone_make_owned_borrowed() {
+        let data = vec![1u8];
+        NoClone::new(MaybeOwned::Borrowed(&data)).make_owned();
+    }
+
+    #[test]
+    fn concat_strings() {
+        let b = String::from("b");
+        let items = [
+            MaybeOwned::Owned(String::from("a")),
+            MaybeOwned::Borrowed(&b),
+            MaybeOwned::Owned(String::from("c")),
+        ];
+        assert_eq!(concat_maybe_owned(&items), "abc");
+        assert_eq!(concat_maybe_owned::<String>(&[]), "");
+    }
+
+    #[test]
+    fn concat_vecs() {
+        let b = vec![2u8, 3];
+        let items = [MaybeOwned::Borrowed(&b), MaybeOwned::Owned(vec![4u8])];
+        assert_eq!(concat_maybe_owned(&items), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn keys_cloned() {
+        use std::collections::BTreeMap;
+
+        let mut hash_map = HashMap::new();
+        hash_map.insert(1u8, ());
+        let mut keys = MaybeOwned::Borrowed(&hash_map).keys_cloned();
+        keys.sort();
+        assert_eq!(keys, vec![1]);
+        assert_eq!(MaybeOwnedMut::Owned(hash_map).keys_cloned(), vec![1]);
+
+        let mut btree_map = BTreeMap::new();
+        btree_map.insert("b", ());
+        btree_map.insert("a", ());
+        assert_eq!(
+            MaybeOwned::Borrowed(&btree_map).keys_cloned(),
+            vec!["a", "b"]
+        );
+        assert_eq!(MaybeOwned::Owned(btree_map).keys_cloned(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn update() {
+        let clones = Cell::new(0);
+        let data = (CloneCounter(&clones), 1u8);
+        let mut maybe = MaybeOwned::Borrowed(&data);
+
+        assert_eq!(maybe.update(|v| v.1 += 1).1, 2);
+        assert!(maybe.is_owned());
+        assert_eq!(clones.get(), 1);
+
+        assert_eq!(maybe.update(|v| v.1 += 1).1, 3);
+        assert_eq!(clones.get(), 1);
+        assert_eq!(data.1, 1);
+    }
+
+    #[test]
+    fn transform() {
+        let clones = Cell::new(0);
+        let mut maybe = MaybeOwned::Owned((CloneCounter(&clones), 1u8));
+        maybe.transform(|v| v.1 += 1);
+        assert_eq!(maybe.1, 2);
+        assert_eq!(clones.get(), 0);
+
+        let data = (CloneCounter(&clones), 1u8);
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        maybe.transform(|v| v.1 += 1);
+        maybe.transform(|v| v.1 += 1);
+        assert!(maybe.is_owned());
+        assert_eq!(maybe.1, 3);
+        assert_eq!(clones.get(), 1);
+        assert_eq!(data.1, 1);
+    }
+
+    #[test]
+    fn view() {
+        let data = 12u8;
+        let maybe = MaybeOwned::Borrowed(&data);
+        assert_eq!(maybe.view(), MaybeOwnedView::Borrowed(&12));
+        assert!(!maybe.view().is_owned());
+        let maybe: MaybeOwned<u8> = MaybeOwned::Owned(13);
+        assert_eq!(maybe.view(), MaybeOwnedView::Owned(&13));
+        assert_eq!(*maybe.view(), 13);
+
+        let mut data = 12u8;
+        let maybe = MaybeOwnedMut::Borrowed(&mut data);
+        assert_eq!(maybe.view(), MaybeOwnedView::Borrowed(&12));
+        let maybe: MaybeOwnedMut<u8> = MaybeOwnedMut::Owned(13);
+        assert!(maybe.view().is_owned());
+    }
+
+    #[test]
+    fn deep_clone() {
+        use std::cell::RefCell;
+
+        let data = Rc::new(vec![1u8]);
+        let maybe = MaybeOwned::Borrowed(&data);
+        let copy = maybe.deep_clone();
+        assert!(copy.is_owned());
+        assert!(!Rc::ptr_eq(&data, &copy));
+        assert_eq!(*copy, data);
+
+        let shared = MaybeOwned::Owned(Rc::new(RefCell::new(1u8)));
+        let copy = shared.deep_clone();
+        *RefCell::borrow_mut(&copy) = 2;
+        assert_eq!(*RefCell::borrow(&shared), 1);
+    }
+
+    #[test]
+    fn cloned() {
+        fn snapshot(maybe: &MaybeOwned<'_, String>) -> MaybeOwned<'static, String> {
+            maybe.cloned()
+        }
+
+        let snap = {
+            let data = "abc".to_owned();
+            let maybe = MaybeOwned::Borrowed(&data);
+            snapshot(&maybe)
+        };
+        assert!(snap.is_owned());
+        assert_eq!(*snap, "abc");
+
+        let maybe: MaybeOwned<String> = MaybeOwned::Owned("def".to_owned());
+        let snap = maybe.cloned();
+        assert!(snap.is_owned());
+        assert_eq!(snap, maybe);
+    }
+
+    #[test]
+    fn take_if_passing() {
+        let data = vec![1u8];
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        let taken = maybe.take_if(|v| v.len() == 1).unwrap();
+        assert!(!taken.is_owned());
+        assert_eq!(*taken, vec![1]);
+        assert!(maybe.is_owned());
+        assert!(maybe.is_empty());
+    }
+
+    #[test]
+    fn take_if_failing() {
+        let mut maybe: MaybeOwned<Vec<u8>> = MaybeOwned::Owned(vec![1]);
+        assert_eq!(maybe.take_if(|v| v.is_empty()), None);
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, vec![1]);
+    }
+
+    #[test]
+    fn first_owned() {
+        let data = 1u8;
+        let items = [MaybeOwned::Borrowed(&data), MaybeOwned::Owned(2)];
+        assert_eq!(super::first_owned(&items), Some(&2));
+        assert_eq!(super::first_owned(&items[..1]), None);
+        assert_eq!(super::first_owned::<u8>(&[]), None);
+    }
+
+    #[test]
+    fn owned_fraction() {
+        let data = 1u8;
+        assert_eq!(super::owned_fraction::<u8>(&[]), 0.0);
+        assert_eq!(
+            super::owned_fraction(&[MaybeOwned::Owned(1), MaybeOwned::Owned(2)]),
+            1.0
+        );
+        assert_eq!(
+            super::owned_fraction(&[MaybeOwned::Borrowed(&data), MaybeOwned::Borrowed(&data)]),
+            0.0
+        );
+        let items = [
+            MaybeOwned::Borrowed(&data),
+            MaybeOwned::Owned(2),
+            MaybeOwned::Borrowed(&data),
+            MaybeOwned::Borrowed(&data),
+        ];
+        assert_eq!(super::owned_fraction(&items), 0.25);
+    }
+
+    #[test]
+    fn coalesce() {
+        let (a, b) = (1u8, 2u8);
+        let candidates = [
+            MaybeOwned::Borrowed(&a),
+            MaybeOwned::Owned(3),
+            MaybeOwned::Borrowed(&b),
+            MaybeOwned::Owned(4),
+        ];
+        assert_eq!(super::coalesce(candidates), Some(MaybeOwned::Owned(3)));
+
+        let candidates = [MaybeOwned::Borrowed(&a), MaybeOwned::Borrowed(&b)];
+        let first = super::coalesce(candidates).unwrap();
+        assert!(std::ptr::eq(&*first, &a));
+
+        assert_eq!(super::coalesce(Vec::<MaybeOwned<u8>>::new()), None);
+    }
+
+    #[test]
+    fn parts_round_trip() {
+        let data = 12u8;
+        let parts = MaybeOwned::Borrowed(&data).into_parts();
+        assert!(!parts.is_owned());
+        assert!(std::ptr::eq(parts.borrowed().unwrap(), &data));
+        let maybe = MaybeOwned::from_parts(parts);
+        assert!(!maybe.is_owned());
+        assert_eq!(*maybe, 12);
+
+        let parts = MaybeOwned::<u8>::Owned(13).into_parts();
+        assert!(parts.is_owned());
+        assert_eq!(parts.borrowed(), None);
+        let maybe = MaybeOwned::from_parts(parts.clone());
+        assert!(maybe.is_owned());
+        assert_eq!(*maybe, 13);
+        assert_eq!(parts.owned(), Some(13));
+    }
+
+    #[test]
+    fn reconcile() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Settings {
+            name: Option<&'static str>,
+            port: Option<u16>,
+        }
+
+        impl ThreeWayMerge for Settings {
+            fn three_way_merge(&mut self, base: &Self, other: &Self) {
+                self.name.three_way_merge(&base.name, &other.name);
+                self.port.three_way_merge(&base.port, &other.port);
+            }
+        }
+
+        let base = Settings {
+            name: Some("base"),
+            port: None,
+        };
+        let ours = Settings {
+            name: Some("ours"),
+            port: None,
+        };
+        let theirs = Settings {
+            name: Some("base"),
+            port: Some(80),
+        };
+
+        let merged = MaybeOwned::reconcile(&base, (&ours).into(), (&theirs).into());
+        assert!(merged.is_owned());
+        assert_eq!(
+            *merged,
+            Settings {
+                name: Some("ours"),
+                port: Some(80),
+            }
+        );
+    }
+
+    #[test]
+    fn cache_evicts_oldest() {
+        let data = 1u8;
+        let mut cache: MaybeOwnedCache<_, u8> = MaybeOwnedCache::new(2);
+        cache.insert(1, MaybeOwned::Borrowed(&data));
+        cache.insert(2, 2);
+        cache.insert(1, 3);
+        assert_eq!(cache.len(), 2);
+
+        cache.insert(3, MaybeOwned::Borrowed(&data));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&2).is_none());
+        assert_eq!(**cache.get(&1).unwrap(), 3);
+        assert!(cache.remove(&3).is_some());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn cache_promotes_borrowed() {
+        let data = vec![1u8];
+        let mut cache: MaybeOwnedCache<_, Vec<u8>> = MaybeOwnedCache::new(2);
+        cache.insert("a", &data);
+        cache.insert("b", &data);
+        assert!(!cache.get(&"a").unwrap().is_owned());
+
+        cache.get_mut(&"a").unwrap().push(2);
+        assert!(cache.get(&"a").unwrap().is_owned());
+        assert_eq!(**cache.get(&"a").unwrap(), vec![1, 2]);
+
+        assert!(cache.refresh(&"b"));
+        assert!(cache.get(&"b").unwrap().is_owned());
+        assert!(!cache.refresh(&"c"));
+        assert_eq!(data, vec![1]);
+    }
+
+    #[test]
+    fn relink() {
+        let (front, back) = (1u8, 2u8);
+        let mut maybe = MaybeOwned::Owned(0u8);
+        maybe.relink(&front);
+        assert!(!maybe.is_owned());
+        assert!(std::ptr::eq(&*maybe, &front));
+        maybe.relink(&back);
+        assert!(std::ptr::eq(&*maybe, &back));
+    }
+
+    #[test]
+    fn byte_len_hint() {
+        let data = vec![1u8, 2];
+        let items = [MaybeOwned::Borrowed(&data), MaybeOwned::Owned(vec![3])];
+        assert_eq!(items.iter().map(|v| v.byte_len_hint()).sum::<usize>(), 3);
+        let maybe: MaybeOwnedMut<String> = MaybeOwnedMut::Owned("abc".into());
+        assert_eq!(maybe.byte_len_hint(), 3);
+    }
+
+    #[test]
+    fn array_chunks() {
+        let data = (0u8..10).collect::<Vec<_>>();
+        let maybe = MaybeOwned::Borrowed(&data);
+        let chunks = maybe.array_chunks::<4>().collect::<Vec<_>>();
+        assert_eq!(chunks, [&[0, 1, 2, 3], &[4, 5, 6, 7]]);
+    }
+
+    #[test]
+    fn try_make_owned() {
+        let data = String::from("data");
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        maybe.try_make_owned().unwrap().push('!');
+        assert_eq!(*maybe, "data!");
+        assert_eq!(data, "data");
+
+        let mut data = vec![1u8];
+        let mut maybe = MaybeOwnedMut::Borrowed(&mut data);
+        maybe.try_make_owned().unwrap().push(2);
+        assert!(maybe.is_owned());
+    }
+
+    #[test]
+    fn try_make_owned_failing() {
+        struct Failing;
+
+        impl TryClone for Failing {
+            fn try_clone(&self) -> Result<Self, TryReserveError> {
+                Err(Vec::<u8>::new().try_reserve(usize::MAX).unwrap_err())
+            }
+        }
+
+        let data = Failing;
+        let mut maybe = MaybeOwned::Borrowed(&data);
+        assert!(maybe.try_make_owned().is_err());
+        assert!(!maybe.is_owned());
+    }
+
+    #[test]
+    fn into_boxed_slice() {
+        let data = vec![1u8, 2];
+        let boxed = MaybeOwned::Borrowed(&data).into_boxed_slice();
+        assert_eq!(&*boxed, &[1, 2]);
+        let boxed = MaybeOwned::Owned(vec![3u8]).into_boxed_slice();
+        assert_eq!(&*boxed, &[3]);
+    }
+
+    #[test]
+    fn count_owned_values() {
+        let data = 1u8;
+        let mut map = HashMap::new();
+        assert_eq!(super::count_owned_values(&map), 0);
+        map.insert("a", MaybeOwned::Borrowed(&data));
+        map.insert("b", MaybeOwned::Owned(2));
+        map.insert("c", MaybeOwned::Owned(3));
+        assert_eq!(super::count_owned_values(&map), 2);
+    }
+
+    #[test]
+    fn maybe_owned_str() {
+        let borrowed = MaybeOwnedStr::from("abc");
+        assert!(!borrowed.is_owned());
+        assert_eq!(borrowed.len(), 3);
+        assert_eq!(format!("{}", borrowed), "abc");
+
+        let mut owned = MaybeOwnedStr::from(String::from("abc"));
+        assert!(owned.is_owned());
+        assert_eq!(owned, borrowed);
+
+        let mut maybe = borrowed.clone();
+        maybe.make_owned().push('d');
+        assert!(maybe.is_owned());
+        assert!(borrowed < maybe);
+        owned.make_owned().push('e');
+        assert_eq!(owned.into_owned(), "abce");
+        assert_eq!(borrowed.into_owned(), "abc");
+    }
+
+    #[test]
+    fn maybe_owned_into_unsized_pair() {
+        let data = String::from("abc");
+        let maybe = MaybeOwnedStr::from(MaybeOwned::Borrowed(&data));
+        assert!(!maybe.is_owned());
+        assert!(std::ptr::eq(&*maybe, data.as_str()));
+        let owned: String = maybe.into_owned();
+        assert_eq!(owned, "abc");
+
+        let maybe = MaybeOwnedStr::from(MaybeOwned::Owned(String::from("def")));
+        assert!(maybe.is_owned());
+        assert_eq!(maybe.into_owned(), "def");
+
+        let data = vec![1u8, 2];
+        let maybe = MaybeOwnedBytes::from(MaybeOwned::Borrowed(&data));
+        assert!(!maybe.is_owned());
+        assert!(std::ptr::eq(&*maybe, &data[..]));
+        let owned: Vec<u8> = maybe.into_owned();
+        assert_eq!(owned, data);
+    }
+
+    #[test]
+    fn maybe_owned_unsized() {
+        fn fields(line: &str) -> Vec<MaybeOwnedUnsized<'_, String, str>> {
+            line.split(',')
+                .map(|field| {
+                    let field = field.trim();
+                    match field.strip_prefix('"').and_then(|f| f.strip_suffix('"')) {
+                        Some(quoted) if quoted.contains('\\') => {
+                            MaybeOwnedUnsized::Owned(quoted.replace("\\\"", "\""))
+                        }
+                        Some(quoted) => MaybeOwnedUnsized::Borrowed(quoted),
+                        None => MaybeOwnedUnsized::Borrowed(field),
+                    }
+                })
+                .collect()
+        }
+
+        let line = String::from(r#""a", 12, "b\"c""#);
+        let mut fields = fields(&line);
+        assert!(fields[0].is_borrowed());
+        assert!(std::ptr::eq(&*fields[0], &line[1..2]));
+        assert_eq!(&*fields[1], "12");
+        assert!(fields[2].is_owned());
+        assert_eq!(fields[2].to_string(), "b\"c");
+
+        fields[0].make_owned().push('d');
+        assert!(fields[0].is_owned());
+        assert_eq!(fields[0], MaybeOwnedUnsized::Borrowed("ad"));
+
+        let owned: Vec<String> = fields.into_iter().map(|f| f.into_owned()).collect();
+        assert_eq!(owned, ["ad", "12", "b\"c"]);
+
+        let data = [1u32, 2];
+        let slice: MaybeOwnedUnsized<Vec<u32>, [u32]> = (&data[..]).into();
+        assert_eq!(slice.len(), 2);
+        assert_eq!(slice.into_owned(), vec![1, 2]);
+    }
+
+    #[test]
+    fn maybe_owned_unsized_clone_and_debug() {
+        let text = String::from("abc");
+        let borrowed: MaybeOwnedUnsized<'_, String, str> = MaybeOwnedUnsized::Borrowed(&text);
+        let cloned = borrowed.clone();
+        assert!(cloned.is_borrowed());
+        assert!(std::ptr::eq(&*cloned, &*text));
+        assert_eq!(format!("{:?}", cloned), r#"Borrowed("abc")"#);
+
+        let owned: MaybeOwnedUnsized<'_, String, str> = MaybeOwnedUnsized::Owned(text.clone());
+        let cloned = owned.clone();
+        assert!(cloned.is_owned());
+        assert!(!std::ptr::eq(&*cloned, &*owned));
+        assert_eq!(cloned, owned);
+        assert_eq!(format!("{:?}", cloned), r#"Owned("abc")"#);
+    }
+
+    #[test]
+    fn partial_eq_bare_value() {
+        let five = 5u32;
+        assert!(MaybeOwned::Owned(5u32) == 5);
+        assert!(MaybeOwned::Borrowed(&five) == 5);
+        assert!(5 == MaybeOwned::Borrowed(&five));
+        assert!(MaybeOwned::Owned(5u32) != 6);
+        assert!(6 != MaybeOwned::Owned(5u32));
+
+        let mut five = 5i64;
+        assert!(MaybeOwnedMut::Borrowed(&mut five) == 5);
+        assert!(5 == MaybeOwnedMut::Owned(5i64));
+
+        let name = String::from("abc");
+        let maybe = MaybeOwned::Borrowed(&name);
+        assert!(maybe == name);
+        assert!(name == maybe);
+        assert!(maybe == "abc");
+        assert!(maybe == *"abc");
+    }
+
+    #[test]
+    fn maybe_owned_bytes() {
+        let data = vec![1u8, 2];
+        let borrowed = MaybeOwnedBytes::from(&data[..]);
+        assert!(!borrowed.is_owned());
+        assert!(std::ptr::eq(borrowed.as_ref(), &data[..]));
+
+        let mut owned = MaybeOwnedBytes::from(vec![1u8, 2]);
+        assert!(owned.is_owned());
+        assert_eq!(owned, borrowed);
+        owned.make_owned().push(3);
+        assert_eq!(owned.into_owned(), vec![1, 2, 3]);
+        assert_eq!(borrowed.into_owned(), data);
+    }
+
+    #[test]
+    fn maybe_owned_path() {
+        use std::path::{Path, PathBuf};
+
+        let borrowed = MaybeOwnedPath::from(Path::new("a/b"));
+        assert!(!borrowed.is_owned());
+        assert_eq!(borrowed.file_name().unwrap(), "b");
+        assert_eq!(borrowed.to_string(), "a/b");
+
+        let mut owned = MaybeOwnedPath::from(PathBuf::from("a/b"));
+        assert!(owned.is_owned());
+        assert_eq!(owned, borrowed);
+        owned.make_owned().push("c");
+        assert_eq!(owned.as_ref(), Path::new("a/b/c"));
+        assert_eq!(borrowed.into_owned(), PathBuf::from("a/b"));
+    }
+
+    #[test]
+    fn partial_ord_bare_value() {
+        let n = 33.0f32;
+        let a = MaybeOwned::Owned(42.0f32);
+        let b = MaybeOwned::Borrowed(&n);
+
+        assert_eq!(a > 33.0, true);
+        assert_eq!(b > 33.0, false);
+        assert_eq!(b >= 33.0, true);
+        assert_eq!(42.0 <= a, true);
+        assert_eq!(40.0 > b, true);
+        assert_eq!(MaybeOwned::Owned(f32::NAN).partial_cmp(&1.0), None);
+
+        let mut n = 5u8;
+        assert!(MaybeOwnedMut::Borrowed(&mut n) < 6);
+
+        let name = String::from("b");
+        assert!(MaybeOwned::Borrowed(&name) > "a");
+    }
+
+    #[test]
+    fn swap_to_borrow() {
+        let (front, back) = (vec![1u8], vec![2u8]);
+        let mut maybe = MaybeOwned::Owned(vec![0u8]);
+        assert_eq!(maybe.swap_to_borrow(&front), Some(vec![0]));
+        assert!(!maybe.is_owned());
+        assert!(std::ptr::eq(&*maybe, &front));
+        assert_eq!(maybe.swap_to_borrow(&back), None);
+        assert!(std::ptr::eq(&*maybe, &back));
+    }
+
+    #[test]
+    fn cow_interop() {
+        let data = vec![1u8];
+        let borrowed = MaybeOwned::Borrowed(&data);
+        let owned: MaybeOwned<Vec<u8>> = MaybeOwned::Owned(vec![2]);
+
+        match (borrowed.borrow_cow_view(), owned.borrow_cow_view()) {
+            (Cow::Borrowed(b), Cow::Borrowed(o)) => {
+                assert!(std::ptr::eq(b, &data));
+                assert!(std::ptr::eq(o, &*owned));
+            }
+            _ => panic!("view should borrow"),
+        }
+
+        match Cow::from(borrowed.clone()) {
+            Cow::Borrowed(b) => assert!(std::ptr::eq(b, &data)),
+            Cow::Owned(_) => panic!("conversion should keep the borrow"),
+        }
+        assert!(matches!(Cow::from(owned.clone()), Cow::Owned(_)));
+
+        let detached = borrowed.into_detached_cow();
+        drop(data);
+        assert!(matches!(detached, Cow::Owned(ref v) if *v == [1]));
+        assert!(matches!(owned.into_detached_cow(), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn map() {
+        let clones = Cell::new(0);
+        let data = (CloneCounter(&clones), 1u8);
+
+        let mapped = MaybeOwned::Borrowed(&data).map(|v| v.1 + 1);
+        assert_eq!(mapped, MaybeOwned::Owned(2));
+        assert_eq!(clones.get(), 1);
+
+        let mapped = MaybeOwned::Owned((CloneCounter(&clones), 3u8)).map(|v| v.1);
+        assert_eq!(*mapped, 3);
+        assert_eq!(clones.get(), 1);
+    }
+
+    #[test]
+    fn map_ref() {
+        let clones = Cell::new(0);
+        let data = (CloneCounter(&clones), 1u8);
+        let maybe = MaybeOwned::Borrowed(&data);
+        let mapped = maybe.map_ref(|v| &v.1);
+        assert!(!mapped.is_owned());
+        assert!(std::ptr::eq(&*mapped, &data.1));
+        assert_eq!(clones.get(), 0);
+    }
+
+    #[test]
+    fn as_deref() {
+        let name = String::from("abc");
+        let maybe = MaybeOwned::Borrowed(&name);
+        let name_str: &str = maybe.as_deref();
+        assert!(std::ptr::eq(name_str, name.as_str()));
+        let maybe: MaybeOwned<String> = MaybeOwned::Owned("def".into());
+        assert_eq!(maybe.as_deref(), "def");
+
+        let mut bytes = vec![1u8, 2];
+        let maybe = MaybeOwnedMut::Borrowed(&mut bytes);
+        let bytes_slice: &[u8] = maybe.as_deref();
+        assert_eq!(bytes_slice, &[1, 2]);
+
+        let boxed = MaybeOwned::Owned(Box::new(3u8));
+        let inner: MaybeOwned<u8> = boxed.as_deref().into();
+        assert!(!inner.is_owned());
+        assert_eq!(*inner, 3);
+    }
+
+    #[test]
+    fn into_arc_memoized() {
+        let clones = Cell::new(0);
+        let data = CloneCounter(&clones);
+        let slot = OnceCell::new();
+
+        let first = MaybeOwned::Borrowed(&data).into_arc_memoized(&slot);
+        let second = MaybeOwned::Borrowed(&data).into_arc_memoized(&slot);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(clones.get(), 1);
+
+        let third = MaybeOwned::Owned(CloneCounter(&clones)).into_arc_memoized(&slot);
+        assert!(Arc::ptr_eq(&first, &third));
+
+        let slot = OnceCell::new();
+        let owned = MaybeOwned::Owned(CloneCounter(&clones)).into_arc_memoized(&slot);
+        assert!(Arc::ptr_eq(&owned, slot.get().unwrap()));
+        assert_eq!(clones.get(), 1);
+    }
+
+    #[test]
+    fn into_iter() {
+        let data = vec![1u8, 2];
+        let mut sum = 0;
+        for v in MaybeOwned::Borrowed(&data) {
+            sum += v;
+        }
+        for v in MaybeOwned::Owned(vec![3u8]) {
+            sum += v;
+        }
+        assert_eq!(sum, 6);
+
+        let mut map = HashMap::new();
+        map.insert("a", 1u8);
+        let collected: Vec<(&str, u8)> = MaybeOwned::Borrowed(&map).into_iter().collect();
+        assert_eq!(collected, vec![("a", 1)]);
+        let collected: Vec<(&str, u8)> = MaybeOwned::Owned(map).into_iter().collect();
+        assert_eq!(collected, vec![("a", 1)]);
+    }
+
+    #[test]
+    fn into_iter_by_ref() {
+        let clones = Cell::new(0);
+        let data = vec![CloneCounter(&clones), CloneCounter(&clones)];
+        let maybe = MaybeOwned::Borrowed(&data);
+        let mut count = 0;
+        for v in &maybe {
+            assert!(std::ptr::eq(v.0, &clones));
+            count += 1;
+        }
+        assert_eq!(count, 2);
+        assert_eq!(clones.get(), 0);
+
+        let mut map = HashMap::new();
+        map.insert("a", 1u8);
+        let maybe: MaybeOwned<HashMap<&str, u8>> = MaybeOwned::Owned(map);
+        for (k, v) in &maybe {
+            assert_eq!((*k, *v), ("a", 1));
+        }
+    }
+
+    #[test]
+    fn checked_arith() {
+        let two = 2u8;
+        let two = MaybeOwned::Borrowed(&two);
+        assert_eq!(
+            two.clone().checked_add(3.into()),
+            Some(MaybeOwned::Owned(5))
+        );
+        assert_eq!(
+            two.clone().checked_sub(2.into()),
+            Some(MaybeOwned::Owned(0))
+        );
+        assert_eq!(
+            two.clone().checked_mul(100.into()),
+            Some(MaybeOwned::Owned(200))
+        );
+        assert_eq!(
+            two.clone().checked_div(two.clone()),
+            Some(MaybeOwned::Owned(1))
+        );
+
+        assert_eq!(MaybeOwned::Owned(255u8).checked_add(two.clone()), None);
+        assert_eq!(MaybeOwned::Owned(1u8).checked_sub(two.clone()), None);
+        assert_eq!(MaybeOwned::Owned(200u8).checked_mul(two.clone()), None);
+        assert_eq!(two.checked_div(0.into()), None);
+        assert_eq!(MaybeOwned::Owned(i8::MIN).checked_div((-1).into()), None);
+    }
+
+    #[test]
+    fn saturating_arith() {
+        let two = 2u8;
+        let two = MaybeOwned::Borrowed(&two);
+        assert_eq!(two.clone().saturating_add(3.into()), 5);
+        assert_eq!(MaybeOwned::Owned(255u8).saturating_add(two.clone()), 255);
+        assert_eq!(MaybeOwned::Owned(1u8).saturating_sub(two.clone()), 0);
+        assert_eq!(MaybeOwned::Owned(200u8).saturating_mul(two.clone()), 255);
+        assert_eq!(MaybeOwned::Owned(i8::MIN).saturating_sub(1.into()), i8::MIN);
+        assert!(two.saturating_mul(3.into()).is_owned());
+    }
+
+    #[test]
+    fn wrapping_arith() {
+        let two = 2u8;
+        let two = MaybeOwned::Borrowed(&two);
+        assert_eq!(two.clone().wrapping_add(3.into()), 5);
+        assert_eq!(MaybeOwned::Owned(255u8).wrapping_add(two.clone()), 1);
+        assert_eq!(MaybeOwned::Owned(1u8).wrapping_sub(two.clone()), 255);
+        assert_eq!(MaybeOwned::Owned(200u8).wrapping_mul(two.clone()), 144);
+        assert_eq!(MaybeOwned::Owned(i8::MAX).wrapping_add(1.into()), i8::MIN);
+        assert!(two.wrapping_mul(3.into()).is_owned());
+    }
 }
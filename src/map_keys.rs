@@ -0,0 +1,35 @@
+//! The `MapKeys` trait used by `keys_cloned`.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Maps whose keys can be cloned into a `Vec`.
+///
+/// This is used by `MaybeOwned::keys_cloned` and `MaybeOwnedMut::keys_cloned`.
+pub trait MapKeys {
+    /// The key type of the map.
+    type Key: Clone;
+
+    /// Returns clones of all keys of the map.
+    fn keys_cloned(&self) -> Vec<Self::Key>;
+}
+
+#[cfg(feature = "std")]
+impl<K: Clone, V, S: BuildHasher> MapKeys for HashMap<K, V, S> {
+    type Key = K;
+
+    fn keys_cloned(&self) -> Vec<K> {
+        self.keys().cloned().collect()
+    }
+}
+
+impl<K: Clone, V> MapKeys for BTreeMap<K, V> {
+    type Key = K;
+
+    fn keys_cloned(&self) -> Vec<K> {
+        self.keys().cloned().collect()
+    }
+}
@@ -0,0 +1,150 @@
+//! `MaybeOwnedUnsized`, an owned-or-borrowed type with distinct owned and
+//! borrowed types.
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Deref;
+
+/// Either owns an `O` or borrows a `B`, where `B` can be unsized.
+///
+/// Unlike `MaybeOwned`, where borrowed and owned data are both `T`, this
+/// allows e.g. borrowing a `str` while owning a `String`. Unlike `Cow` the
+/// owned and borrowed types are independent, only some methods require
+/// `O: Borrow<B>` or `B: ToOwned<Owned = O>`.
+///
+/// For the common pairs `MaybeOwnedStr`, `MaybeOwnedBytes` and
+/// `MaybeOwnedPath` provide more trait implementations.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::MaybeOwnedUnsized;
+///
+/// // strings without escapes can be borrowed from the input
+/// fn unescape(raw: &str) -> MaybeOwnedUnsized<'_, String, str> {
+///     if raw.contains('\\') {
+///         MaybeOwnedUnsized::Owned(raw.replace('\\', ""))
+///     } else {
+///         MaybeOwnedUnsized::Borrowed(raw)
+///     }
+/// }
+///
+/// assert!(!unescape("abc").is_owned());
+/// assert_eq!(&*unescape(r"a\,c"), "a,c");
+/// let owned: String = unescape("abc").into_owned();
+/// assert_eq!(owned, "abc");
+/// ```
+pub enum MaybeOwnedUnsized<'a, O, B: ?Sized + 'a> {
+    /// owns the data
+    Owned(O),
+    /// has a reference to the data
+    Borrowed(&'a B),
+}
+
+// not derived, as that would require `B: Clone` which unsized types can't
+// implement
+impl<O: Clone, B: ?Sized> Clone for MaybeOwnedUnsized<'_, O, B> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Owned(v) => Self::Owned(v.clone()),
+            Self::Borrowed(v) => Self::Borrowed(v),
+        }
+    }
+}
+
+impl<O: fmt::Debug, B: ?Sized + fmt::Debug> fmt::Debug for MaybeOwnedUnsized<'_, O, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Owned(v) => f.debug_tuple("Owned").field(v).finish(),
+            Self::Borrowed(v) => f.debug_tuple("Borrowed").field(v).finish(),
+        }
+    }
+}
+
+impl<'a, O, B: ?Sized> MaybeOwnedUnsized<'a, O, B> {
+    /// Returns true if the data is owned else false.
+    pub fn is_owned(&self) -> bool {
+        match self {
+            Self::Owned(_) => true,
+            Self::Borrowed(_) => false,
+        }
+    }
+
+    /// Returns true if the data is borrowed else false.
+    pub fn is_borrowed(&self) -> bool {
+        !self.is_owned()
+    }
+
+    /// Returns the reference if the data is borrowed.
+    ///
+    /// If the data is owned it's returned as error, so it's not lost.
+    pub fn try_into_borrowed(self) -> Result<&'a B, O> {
+        match self {
+            Self::Owned(v) => Err(v),
+            Self::Borrowed(v) => Ok(v),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<O, B: ?Sized + ToOwned<Owned = O>> MaybeOwnedUnsized<'_, O, B> {
+    /// Return the contained data in it's owned form.
+    ///
+    /// If it's borrowed this will create the owned data with `to_owned`.
+    pub fn into_owned(self) -> O {
+        match self {
+            Self::Owned(v) => v,
+            Self::Borrowed(v) => v.to_owned(),
+        }
+    }
+
+    /// Internally converts it into it's owned variant and returns a
+    /// `&mut` to the owned data.
+    pub fn make_owned(&mut self) -> &mut O {
+        if let Self::Borrowed(v) = self {
+            *self = Self::Owned(v.to_owned());
+        }
+        match self {
+            Self::Owned(v) => v,
+            Self::Borrowed(..) => unreachable!(),
+        }
+    }
+}
+
+impl<O: Borrow<B>, B: ?Sized> Deref for MaybeOwnedUnsized<'_, O, B> {
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        match self {
+            Self::Owned(v) => v.borrow(),
+            Self::Borrowed(v) => v,
+        }
+    }
+}
+
+impl<O: Borrow<B>, B: ?Sized> AsRef<B> for MaybeOwnedUnsized<'_, O, B> {
+    fn as_ref(&self) -> &B {
+        self
+    }
+}
+
+impl<'a, O, B: ?Sized> From<&'a B> for MaybeOwnedUnsized<'a, O, B> {
+    fn from(v: &'a B) -> Self {
+        Self::Borrowed(v)
+    }
+}
+
+impl<O: Borrow<B>, B: ?Sized + PartialEq> PartialEq for MaybeOwnedUnsized<'_, O, B> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<O: Borrow<B>, B: ?Sized + Eq> Eq for MaybeOwnedUnsized<'_, O, B> {}
+
+impl<O: Borrow<B>, B: ?Sized + fmt::Display> fmt::Display for MaybeOwnedUnsized<'_, O, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,65 @@
+//! The `Merge` and `ThreeWayMerge` traits used by `MaybeOwned::merge` and
+//! `MaybeOwned::reconcile`.
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Types which can merge another value into themselves.
+///
+/// This is used by [`MaybeOwned::merge`](crate::MaybeOwned::merge) to
+/// e.g. merge layers of a configuration.
+pub trait Merge {
+    /// Merges `other` into `self`.
+    fn merge(&mut self, other: Self);
+}
+
+/// Merges by extending the map, entries of `other` replace existing ones.
+#[cfg(feature = "std")]
+impl<K: Eq + Hash, V, S: BuildHasher> Merge for HashMap<K, V, S> {
+    fn merge(&mut self, other: Self) {
+        self.extend(other)
+    }
+}
+
+/// Types which can merge the changes another value made relative to a
+/// common base into themselves.
+///
+/// This is used by [`MaybeOwned::reconcile`](crate::MaybeOwned::reconcile)
+/// e.g. to reconcile settings changed in two places.
+///
+/// For structs with mostly `Option` fields it can be implemented by
+/// merging field by field using the implementation for `Option`.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::ThreeWayMerge;
+///
+/// #[derive(Clone)]
+/// struct Settings {
+///     name: Option<String>,
+///     port: Option<u16>,
+/// }
+///
+/// impl ThreeWayMerge for Settings {
+///     fn three_way_merge(&mut self, base: &Self, other: &Self) {
+///         self.name.three_way_merge(&base.name, &other.name);
+///         self.port.three_way_merge(&base.port, &other.port);
+///     }
+/// }
+/// ```
+pub trait ThreeWayMerge {
+    /// Merges the changes `other` made relative to `base` into `self`.
+    fn three_way_merge(&mut self, base: &Self, other: &Self);
+}
+
+/// Takes the value of `other` if it differs from `base`, so changes of
+/// `other` win over changes of `self`.
+impl<T: Clone + PartialEq> ThreeWayMerge for Option<T> {
+    fn three_way_merge(&mut self, base: &Self, other: &Self) {
+        if other != base {
+            self.clone_from(other);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! A `MaybeOwned` wrapper asserting that borrowed data is never cloned.
+use core::ops::Deref;
+
+use MaybeOwned;
+
+/// Wrapper around a `MaybeOwned` which panics if borrowed data would be
+/// cloned by `into_owned` or `make_owned`.
+///
+/// The check is a `debug_assert!`, so it turns accidental clones into a
+/// loud failure during testing while compiling to nothing in release builds.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::{MaybeOwned, NoClone};
+///
+/// let mut maybe = NoClone::new(MaybeOwned::Owned(vec![1u8]));
+/// // fine as the data is already owned, for borrowed data this would panic
+/// maybe.make_owned().push(2);
+/// assert_eq!(maybe.into_owned(), vec![1, 2]);
+/// ```
+#[derive(Debug)]
+pub struct NoClone<'a, T: 'a>(MaybeOwned<'a, T>);
+
+impl<'a, T> NoClone<'a, T> {
+    /// Wraps the given `MaybeOwned`.
+    pub fn new(inner: MaybeOwned<'a, T>) -> Self {
+        NoClone(inner)
+    }
+
+    /// Returns the wrapped `MaybeOwned`.
+    pub fn into_inner(self) -> MaybeOwned<'a, T> {
+        self.0
+    }
+
+    /// Returns true if the data is owned else false.
+    pub fn is_owned(&self) -> bool {
+        self.0.is_owned()
+    }
+
+    fn assert_owned(&self) {
+        debug_assert!(
+            self.0.is_owned(),
+            "borrowed data of a `NoClone` was about to be cloned"
+        );
+    }
+}
+
+impl<'a, T: Clone> NoClone<'a, T> {
+    /// Like `MaybeOwned::into_owned` but panics (with debug assertions)
+    /// if the data is borrowed.
+    pub fn into_owned(self) -> T {
+        self.assert_owned();
+        self.0.into_owned()
+    }
+
+    /// Like `MaybeOwned::make_owned` but panics (with debug assertions)
+    /// if the data is borrowed.
+    pub fn make_owned(&mut self) -> &mut T {
+        self.assert_owned();
+        self.0.make_owned()
+    }
+}
+
+impl<'a, T> From<MaybeOwned<'a, T>> for NoClone<'a, T> {
+    fn from(v: MaybeOwned<'a, T>) -> Self {
+        NoClone(v)
+    }
+}
+
+impl<T> Deref for NoClone<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
@@ -0,0 +1,91 @@
+//! Methods applying the transitive `std::ops` implementations with a
+//! bare (non `MaybeOwned`) right hand side.
+//!
+//! `MaybeOwned<T> + T` can't be implemented without conflicting with the
+//! transitive implementations, so instead methods like `add_val` wrap the
+//! right hand side in `Owned` and use the transitive implementation.
+use core::ops::*;
+
+use {MaybeOwned, MaybeOwnedMut};
+
+macro_rules! impl_val_ops {
+    ($Name:ident: $([
+        $OP:ident : $op:ident => $op_val:ident,
+        $OP_ASSIGN:ident : $op_assign:ident => $op_assign_val:ident
+    ]),*) => {
+        impl<'a, L> $Name<'a, L> {
+            $(
+                #[doc = concat!("Applies `", stringify!($OP), "` with a bare right hand side.")]
+                ///
+                /// The right hand side is wrapped in `Owned`, the result is owned.
+                pub fn $op_val<R: 'a, OUT>(self, rhs: R) -> $Name<'a, OUT>
+                where
+                    Self: $OP<$Name<'a, R>, Output = $Name<'a, OUT>>,
+                {
+                    $OP::$op(self, $Name::Owned(rhs))
+                }
+
+                #[doc = concat!("Applies `", stringify!($OP_ASSIGN), "` with a bare right hand side.")]
+                ///
+                /// The right hand side is wrapped in `Owned`.
+                pub fn $op_assign_val<R: 'a>(&mut self, rhs: R)
+                where
+                    Self: $OP_ASSIGN<$Name<'a, R>>,
+                {
+                    $OP_ASSIGN::$op_assign(self, $Name::Owned(rhs))
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! impl_val_ops_for {
+    ($($Name:ident),*) => {$(
+        impl_val_ops! { $Name:
+            [Add: add => add_val, AddAssign: add_assign => add_assign_val],
+            [Sub: sub => sub_val, SubAssign: sub_assign => sub_assign_val],
+            [Mul: mul => mul_val, MulAssign: mul_assign => mul_assign_val],
+            [Div: div => div_val, DivAssign: div_assign => div_assign_val],
+            [Shl: shl => shl_val, ShlAssign: shl_assign => shl_assign_val],
+            [Shr: shr => shr_val, ShrAssign: shr_assign => shr_assign_val],
+            [BitAnd: bitand => bitand_val, BitAndAssign: bitand_assign => bitand_assign_val],
+            [BitOr: bitor => bitor_val, BitOrAssign: bitor_assign => bitor_assign_val],
+            [BitXor: bitxor => bitxor_val, BitXorAssign: bitxor_assign => bitxor_assign_val]
+        }
+    )*};
+}
+
+impl_val_ops_for!(MaybeOwned, MaybeOwnedMut);
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn add_val() {
+        let five = 5u32;
+        let maybe = MaybeOwned::Borrowed(&five);
+        let sum = maybe.add_val(3);
+        assert!(sum.is_owned());
+        assert_eq!(sum, 8);
+        assert_eq!(MaybeOwned::Owned(5u32).sub_val(3).mul_val(4), 8);
+
+        let mut five = 5u32;
+        assert_eq!(MaybeOwnedMut::Borrowed(&mut five).shl_val(1u32), 10u32);
+    }
+
+    #[test]
+    fn add_assign_val() {
+        let five = 5u32;
+        let mut maybe = MaybeOwned::Borrowed(&five);
+        maybe.add_assign_val(3);
+        assert!(maybe.is_owned());
+        assert_eq!(maybe, 8);
+        assert_eq!(five, 5);
+
+        let mut five = 5u32;
+        let mut maybe = MaybeOwnedMut::Borrowed(&mut five);
+        maybe.bitor_assign_val(2);
+        assert_eq!(five, 7);
+    }
+}
@@ -0,0 +1,45 @@
+//! The parts a `MaybeOwned` can be decomposed into.
+
+/// The ownership flag and data of a `MaybeOwned` as returned by
+/// [`MaybeOwned::into_parts`](crate::MaybeOwned::into_parts).
+///
+/// This gives custom (non serde) encoders full control over how a
+/// `MaybeOwned` is encoded, use
+/// [`MaybeOwned::from_parts`](crate::MaybeOwned::from_parts) to
+/// reconstruct it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipParts<'a, T: 'a> {
+    value: Result<T, &'a T>,
+}
+
+impl<'a, T> OwnershipParts<'a, T> {
+    /// Creates the parts of a `MaybeOwned` owning `value`.
+    pub fn from_owned(value: T) -> Self {
+        OwnershipParts { value: Ok(value) }
+    }
+
+    /// Creates the parts of a `MaybeOwned` borrowing `value`.
+    pub fn from_borrowed(value: &'a T) -> Self {
+        OwnershipParts { value: Err(value) }
+    }
+
+    /// Returns true if the data is owned else false.
+    pub fn is_owned(&self) -> bool {
+        self.value.is_ok()
+    }
+
+    /// Returns the owned data, or `None` if it's borrowed.
+    pub fn owned(self) -> Option<T> {
+        self.value.ok()
+    }
+
+    /// Returns the borrowed reference, or `None` if the data is owned.
+    pub fn borrowed(&self) -> Option<&'a T> {
+        self.value.as_ref().err().copied()
+    }
+
+    /// Returns the owned data as `Ok` or the borrowed reference as `Err`.
+    pub fn into_result(self) -> Result<T, &'a T> {
+        self.value
+    }
+}
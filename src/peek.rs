@@ -0,0 +1,68 @@
+//! A guard for reading a `MaybeOwned` and only promoting it to owned if needed.
+use core::fmt;
+use core::ops::Deref;
+
+use MaybeOwned;
+
+/// Shared access to the data of a `MaybeOwned` which can be turned into
+/// mutable access on demand.
+///
+/// Reading through the guard never clones the data, only the first
+/// mutable access will clone it if it's borrowed.
+///
+/// This is returned by [`MaybeOwned::peek_mut`].
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::MaybeOwned;
+///
+/// let data = vec![1u8, 2, 3];
+/// let mut maybe: MaybeOwned<Vec<u8>> = (&data).into();
+///
+/// let mut peek = maybe.peek_mut();
+/// if peek.len() > 5 {
+///     peek.to_mut().truncate(5);
+/// }
+/// assert!(!maybe.is_owned());
+/// ```
+pub struct PeekMut<'s, 'a: 's, T: 'a> {
+    inner: &'s mut MaybeOwned<'a, T>,
+}
+
+impl<'s, 'a, T> PeekMut<'s, 'a, T> {
+    pub(crate) fn new(inner: &'s mut MaybeOwned<'a, T>) -> Self {
+        PeekMut { inner }
+    }
+
+    /// Returns true if the data is owned, i.e. getting mutable access won't clone.
+    pub fn is_owned(&self) -> bool {
+        self.inner.is_owned()
+    }
+}
+
+impl<'s, 'a, T: Clone> PeekMut<'s, 'a, T> {
+    /// Returns a `&mut T`, cloning the data first if it's borrowed.
+    pub fn to_mut(&mut self) -> &mut T {
+        self.inner.make_owned()
+    }
+
+    /// Converts the guard into a `&mut T`, cloning the data first if it's borrowed.
+    pub fn into_mut(self) -> &'s mut T {
+        self.inner.make_owned()
+    }
+}
+
+impl<'s, 'a, T> Deref for PeekMut<'s, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.inner
+    }
+}
+
+impl<'s, 'a, T: fmt::Debug> fmt::Debug for PeekMut<'s, 'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PeekMut").field(&self.inner).finish()
+    }
+}
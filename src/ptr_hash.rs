@@ -0,0 +1,80 @@
+//! A `MaybeOwned` wrapper using pointer identity for borrowed data.
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::ptr;
+
+use MaybeOwned;
+
+/// Wrapper around a `MaybeOwned` which uses pointer identity for the
+/// borrowed variant in it's `Hash` and `Eq` implementations.
+///
+/// Owned data is hashed and compared by value, borrowed data by the
+/// address it points to. An owned and a borrowed value are never equal.
+///
+/// This is useful for identity keyed caches of (e.g. interned) data which
+/// is expensive to hash.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::{MaybeOwned, MaybeOwnedPtrHash};
+/// use std::collections::HashSet;
+///
+/// let a = String::from("abc");
+/// let b = String::from("abc");
+///
+/// let mut set = HashSet::new();
+/// set.insert(MaybeOwnedPtrHash::from(MaybeOwned::Borrowed(&a)));
+/// assert!(set.contains(&MaybeOwned::Borrowed(&a).into()));
+/// assert!(!set.contains(&MaybeOwned::Borrowed(&b).into()));
+/// ```
+#[derive(Debug)]
+pub struct MaybeOwnedPtrHash<'a, T: 'a>(pub MaybeOwned<'a, T>);
+
+impl<'a, T> MaybeOwnedPtrHash<'a, T> {
+    /// Returns the wrapped `MaybeOwned`.
+    pub fn into_inner(self) -> MaybeOwned<'a, T> {
+        self.0
+    }
+}
+
+impl<'a, T> From<MaybeOwned<'a, T>> for MaybeOwnedPtrHash<'a, T> {
+    fn from(v: MaybeOwned<'a, T>) -> Self {
+        MaybeOwnedPtrHash(v)
+    }
+}
+
+impl<'a, T> Deref for MaybeOwnedPtrHash<'a, T> {
+    type Target = MaybeOwned<'a, T>;
+
+    fn deref(&self) -> &MaybeOwned<'a, T> {
+        &self.0
+    }
+}
+
+impl<T: PartialEq> PartialEq for MaybeOwnedPtrHash<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (MaybeOwned::Owned(l), MaybeOwned::Owned(r)) => l == r,
+            (MaybeOwned::Borrowed(l), MaybeOwned::Borrowed(r)) => ptr::eq(*l, *r),
+            _ => false,
+        }
+    }
+}
+
+impl<T: Eq> Eq for MaybeOwnedPtrHash<'_, T> {}
+
+impl<T: Hash> Hash for MaybeOwnedPtrHash<'_, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match &self.0 {
+            MaybeOwned::Owned(v) => {
+                state.write_u8(0);
+                v.hash(state)
+            }
+            MaybeOwned::Borrowed(v) => {
+                state.write_u8(1);
+                ptr::hash(*v, state)
+            }
+        }
+    }
+}
@@ -1,7 +1,21 @@
-//! Serde `Serialize` and `Deserialize` implementations for `MaybeOwned`.
+//! Serde `Serialize` and `Deserialize` implementations for `MaybeOwned`,
+//! `MaybeOwnedStr`, `MaybeOwnedBytes` and serde helper functions.
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use serde::de::{Error, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use {MaybeOwned, MaybeOwnedMut};
+#[cfg(feature = "alloc")]
+use {MaybeOwnedBytes, MaybeOwnedStr};
 
 macro_rules! serde_impls {
     ($Name:ident) => {
@@ -27,3 +41,151 @@ macro_rules! serde_impls {
 
 serde_impls!(MaybeOwned);
 serde_impls!(MaybeOwnedMut);
+
+#[cfg(feature = "alloc")]
+impl Serialize for MaybeOwnedBytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self)
+    }
+}
+
+/// Borrows the input if the deserializer provides borrowed bytes or
+/// a borrowed string, otherwise the bytes are copied.
+#[cfg(feature = "alloc")]
+impl<'de: 'a, 'a> Deserialize<'de> for MaybeOwnedBytes<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct BytesVisitor;
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = MaybeOwnedBytes<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("bytes")
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedBytes::Borrowed(v))
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedBytes::Owned(v.to_owned()))
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedBytes::Owned(v))
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedBytes::Borrowed(v.as_bytes()))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedBytes::Owned(v.as_bytes().to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedBytes::Owned(v.into_bytes()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            out.push(byte);
+        }
+        Ok(MaybeOwnedBytes::Owned(out))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for MaybeOwnedStr<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+/// Borrows the input if the deserializer provides a borrowed string,
+/// e.g. `serde_json` for strings without escape sequences, otherwise
+/// the string is copied.
+#[cfg(feature = "alloc")]
+impl<'de: 'a, 'a> Deserialize<'de> for MaybeOwnedStr<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct StrVisitor;
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for StrVisitor {
+    type Value = MaybeOwnedStr<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedStr::Borrowed(v))
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedStr::Owned(v.to_owned()))
+    }
+
+    fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(MaybeOwnedStr::Owned(v))
+    }
+}
+
+/// Serializes the data of a `MaybeOwned` from an owned value, cloning
+/// borrowed data first.
+///
+/// This is meant to be used with `#[serde(serialize_with = "...")]` if the
+/// serialized form must come from an owned value, e.g. because the `Clone`
+/// implementation of the data normalizes or validates it.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate maybe_owned;
+/// # extern crate serde_derive;
+/// # extern crate serde_json;
+/// # #[cfg(feature = "serde")]
+/// # fn main() {
+/// # use serde_derive::Serialize;
+/// use maybe_owned::MaybeOwned;
+///
+/// #[derive(Serialize)]
+/// struct Message<'a> {
+///     #[serde(serialize_with = "maybe_owned::serialize_owned")]
+///     body: MaybeOwned<'a, String>,
+/// }
+///
+/// let body = String::from("hy");
+/// let json = serde_json::to_string(&Message { body: (&body).into() }).unwrap();
+/// assert_eq!(json, r#"{"body":"hy"}"#);
+/// # }
+/// # #[cfg(not(feature = "serde"))] fn main() {}
+/// ```
+pub fn serialize_owned<T, S>(value: &MaybeOwned<'_, T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + Clone,
+    S: Serializer,
+{
+    match value {
+        MaybeOwned::Owned(v) => v.serialize(serializer),
+        MaybeOwned::Borrowed(v) => (*v).clone().serialize(serializer),
+    }
+}
@@ -0,0 +1,129 @@
+//! Serde functions which keep the ownership state of a `MaybeOwned`.
+//!
+//! The `Serialize` and `Deserialize` implementations of `MaybeOwned` are
+//! transparent, i.e. they only (de-)serialize the data. This module can
+//! be used with `#[serde(with = "maybe_owned::serde_tagged")]` to instead
+//! (de-)serialize it as an externally tagged enum, e.g. `{"Owned": 12}` or
+//! `{"Borrowed": 12}` with `serde_json`.
+//!
+//! As there is nothing to borrow from when deserializing, both tags are
+//! deserialized into an owned value. Use [`deserialize_tag`] to find out
+//! which tag was used.
+//!
+//! ## Example
+//!
+//! ```
+//! # extern crate maybe_owned;
+//! # extern crate serde_derive;
+//! # extern crate serde_json;
+//! # #[cfg(feature = "serde")]
+//! # fn main() {
+//! # use serde_derive::{Deserialize, Serialize};
+//! use maybe_owned::MaybeOwned;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Entry<'a> {
+//!     #[serde(with = "maybe_owned::serde_tagged")]
+//!     value: MaybeOwned<'a, u32>,
+//! }
+//!
+//! let value = 12;
+//! let json = serde_json::to_string(&Entry { value: (&value).into() }).unwrap();
+//! assert_eq!(json, r#"{"value":{"Borrowed":12}}"#);
+//!
+//! let entry: Entry = serde_json::from_str(&json).unwrap();
+//! assert_eq!(*entry.value, 12);
+//! # }
+//! # #[cfg(not(feature = "serde"))] fn main() {}
+//! ```
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{EnumAccess, Error, VariantAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use MaybeOwned;
+
+const NAME: &str = "MaybeOwned";
+const VARIANTS: &[&str] = &["Owned", "Borrowed"];
+
+/// Serializes a `MaybeOwned` as an enum tagged with `Owned`/`Borrowed`.
+pub fn serialize<T, S>(value: &MaybeOwned<'_, T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    match value {
+        MaybeOwned::Owned(v) => serializer.serialize_newtype_variant(NAME, 0, VARIANTS[0], v),
+        MaybeOwned::Borrowed(v) => serializer.serialize_newtype_variant(NAME, 1, VARIANTS[1], v),
+    }
+}
+
+/// Deserializes a `MaybeOwned` serialized by [`serialize`], the result is
+/// always owned.
+pub fn deserialize<'de, 'a, T, D>(deserializer: D) -> Result<MaybeOwned<'a, T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserialize_tag(deserializer).map(|(_, value)| MaybeOwned::Owned(value))
+}
+
+/// Deserializes the data of a `MaybeOwned` serialized by [`serialize`]
+/// together with a flag which is true if it was tagged as owned.
+pub fn deserialize_tag<'de, T, D>(deserializer: D) -> Result<(bool, T), D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_enum(NAME, VARIANTS, TaggedVisitor(PhantomData))
+}
+
+struct Tag(bool);
+
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(TagVisitor)
+    }
+}
+
+struct TagVisitor;
+
+impl<'de> Visitor<'de> for TagVisitor {
+    type Value = Tag;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("`Owned` or `Borrowed`")
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Tag, E> {
+        match v {
+            0 => Ok(Tag(true)),
+            1 => Ok(Tag(false)),
+            _ => Err(E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Tag, E> {
+        match v {
+            "Owned" => Ok(Tag(true)),
+            "Borrowed" => Ok(Tag(false)),
+            _ => Err(E::unknown_variant(v, VARIANTS)),
+        }
+    }
+}
+
+struct TaggedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for TaggedVisitor<T> {
+    type Value = (bool, T);
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("enum MaybeOwned")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let (Tag(owned), variant) = data.variant()?;
+        Ok((owned, variant.newtype_variant()?))
+    }
+}
@@ -0,0 +1,29 @@
+//! Helpers for testing code which uses `MaybeOwned`.
+use MaybeOwned;
+
+/// Calls `f` once with an owned and once with a borrowed `MaybeOwned` of
+/// `value`.
+///
+/// The borrowed variant borrows a clone of `value` local to this function.
+/// This is meant to reduce the boilerplate of tests which should pass for
+/// both variants.
+///
+/// Requires the `test-util` feature.
+///
+/// # Example
+///
+/// ```
+/// # use maybe_owned::for_each_variant;
+/// for_each_variant(vec![1u8, 2], |maybe| {
+///     assert_eq!(maybe.len(), 2);
+/// });
+/// ```
+pub fn for_each_variant<T, F>(value: T, f: F)
+where
+    T: Clone,
+    F: Fn(MaybeOwned<'_, T>),
+{
+    let borrowed = value.clone();
+    f(MaybeOwned::Owned(value));
+    f(MaybeOwned::Borrowed(&borrowed));
+}
@@ -1,4 +1,4 @@
-use std::ops::*;
+use core::ops::*;
 
 use super::{MaybeOwned, MaybeOwnedMut};
 
@@ -83,79 +83,80 @@ impl_op! {
     [BitXor: bitxor, BitXorAssign: bitxor_assign]
 }
 
-impl<'l, V, OUT> Neg for MaybeOwned<'l, V>
+impl<'l, V, OUT: 'l> Neg for MaybeOwned<'l, V>
 where
     V: Neg<Output = OUT>,
     &'l V: Neg<Output = OUT>,
 {
-    type Output = OUT;
+    type Output = MaybeOwned<'l, OUT>;
 
-    //TODO this should return a MaybeOwned
     fn neg(self) -> Self::Output {
         use self::MaybeOwned::*;
 
-        match self {
+        let result = match self {
             Owned(s) => s.neg(),
             Borrowed(s) => s.neg(),
-        }
+        };
+        Owned(result)
     }
 }
 
-impl<'l, V, OUT> Neg for MaybeOwnedMut<'l, V>
+impl<'l, V, OUT: 'l> Neg for MaybeOwnedMut<'l, V>
 where
     V: Neg<Output = OUT>,
     &'l V: Neg<Output = OUT>,
 {
-    type Output = OUT;
+    type Output = MaybeOwnedMut<'l, OUT>;
 
-    //TODO this should return a MaybeOwned
     fn neg(self) -> Self::Output {
         use self::MaybeOwnedMut::*;
 
-        match self {
+        let result = match self {
             Owned(s) => s.neg(),
             Borrowed(s) => (&*s).neg(),
-        }
+        };
+        Owned(result)
     }
 }
 
-impl<'l, V, OUT> Not for MaybeOwned<'l, V>
+impl<'l, V, OUT: 'l> Not for MaybeOwned<'l, V>
 where
     V: Not<Output = OUT>,
     &'l V: Not<Output = OUT>,
 {
-    type Output = V::Output;
+    type Output = MaybeOwned<'l, OUT>;
 
-    //TODO this should return a MaybeOwned
     fn not(self) -> Self::Output {
         use self::MaybeOwned::*;
 
-        match self {
+        let result = match self {
             Owned(s) => s.not(),
             Borrowed(s) => s.not(),
-        }
+        };
+        Owned(result)
     }
 }
 
-impl<'l, V, OUT> Not for MaybeOwnedMut<'l, V>
+impl<'l, V, OUT: 'l> Not for MaybeOwnedMut<'l, V>
 where
     V: Not<Output = OUT>,
     &'l V: Not<Output = OUT>,
 {
-    type Output = V::Output;
+    type Output = MaybeOwnedMut<'l, OUT>;
 
-    //TODO this should return a MaybeOwned
     fn not(self) -> Self::Output {
         use self::MaybeOwnedMut::*;
 
-        match self {
+        let result = match self {
             Owned(s) => s.not(),
             Borrowed(s) => (&*s).not(),
-        }
+        };
+        Owned(result)
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
     use super::*;
     use std::ops::{Add, AddAssign, Neg, Not};
@@ -191,14 +192,14 @@ mod test {
             self.x += rhs.x
         }
     }
-    impl<'a> Add<Thing> for &'a Thing {
+    impl Add<Thing> for &Thing {
         type Output = u8;
 
         fn add(self, rhs: Thing) -> Self::Output {
             self.x + rhs.x
         }
     }
-    impl<'a, 'b> Add<&'a Thing> for &'b Thing {
+    impl<'a> Add<&'a Thing> for &Thing {
         type Output = u8;
 
         fn add(self, rhs: &'a Thing) -> Self::Output {
@@ -214,7 +215,7 @@ mod test {
         }
     }
 
-    impl<'a> Not for &'a Thing {
+    impl Not for &Thing {
         type Output = bool;
 
         fn not(self) -> Self::Output {
@@ -230,7 +231,7 @@ mod test {
         }
     }
 
-    impl<'a> Neg for &'a Thing {
+    impl Neg for &Thing {
         type Output = i8;
 
         fn neg(self) -> Self::Output {
@@ -305,8 +306,8 @@ mod test {
         let a = Thing { x: 5 };
         let a1: MaybeOwned<Thing> = (&a).into();
         let a2: MaybeOwned<Thing> = (&a).into();
-        assert_eq!(!a1, true);
-        assert_eq!(-a2, -5i8);
+        assert_eq!(!a1, MaybeOwned::Owned(true));
+        assert_eq!(-a2, MaybeOwned::Owned(-5i8));
     }
 
     #[test]
@@ -316,10 +317,63 @@ mod test {
         let a1: MaybeOwnedMut<Thing> = (&mut a).into();
         let b1: MaybeOwnedMut<Thing> = (&mut b).into();
 
-        assert_eq!(!a1, true);
-        assert_eq!(!b1, false);
+        assert_eq!(!a1, MaybeOwnedMut::Owned(true));
+        assert_eq!(!b1, MaybeOwnedMut::Owned(false));
 
         let a2: MaybeOwnedMut<Thing> = (&mut a).into();
-        assert_eq!(-a2, -5i8);
+        assert_eq!(-a2, MaybeOwnedMut::Owned(-5i8));
+    }
+
+    #[test]
+    fn op_assign_clones_only_borrowed() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        struct Counted {
+            x: u8,
+            clones: Rc<Cell<usize>>,
+        }
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                self.clones.set(self.clones.get() + 1);
+                Counted {
+                    x: self.x,
+                    clones: self.clones.clone(),
+                }
+            }
+        }
+
+        impl AddAssign<u8> for Counted {
+            fn add_assign(&mut self, rhs: u8) {
+                self.x += rhs
+            }
+        }
+
+        impl<'a> AddAssign<&'a u8> for Counted {
+            fn add_assign(&mut self, rhs: &'a u8) {
+                self.x += *rhs
+            }
+        }
+
+        let clones = Rc::new(Cell::new(0));
+        let new = |x| Counted {
+            x,
+            clones: clones.clone(),
+        };
+
+        let mut a = MaybeOwned::Owned(new(1));
+        a += MaybeOwned::Owned(2);
+        a += MaybeOwned::Borrowed(&3);
+        assert_eq!(a.x, 6);
+        assert_eq!(clones.get(), 0);
+
+        let b = new(1);
+        let mut b = MaybeOwned::Borrowed(&b);
+        b += MaybeOwned::Owned(2);
+        assert_eq!(clones.get(), 1);
+        b += MaybeOwned::Owned(3);
+        assert_eq!(clones.get(), 1);
+        assert_eq!(b.x, 6);
     }
 }
@@ -0,0 +1,32 @@
+//! The `TryClone` trait used by `try_make_owned`.
+use alloc::collections::TryReserveError;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Types which can be cloned with fallible allocation.
+///
+/// This is used by `MaybeOwned::try_make_owned` and
+/// `MaybeOwnedMut::try_make_owned` for clone-on-write which doesn't
+/// abort if allocating fails.
+pub trait TryClone: Sized {
+    /// Returns a clone or an error if allocating memory for it failed.
+    fn try_clone(&self) -> Result<Self, TryReserveError>;
+}
+
+impl<T: Clone> TryClone for Vec<T> {
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let mut out = Vec::new();
+        out.try_reserve_exact(self.len())?;
+        out.extend_from_slice(self);
+        Ok(out)
+    }
+}
+
+impl TryClone for String {
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let mut out = String::new();
+        out.try_reserve_exact(self.len())?;
+        out.push_str(self);
+        Ok(out)
+    }
+}
@@ -0,0 +1,217 @@
+//! Owned-or-borrowed types for common unsized types and their owned
+//! counterpart, like `str`/`String`, `[u8]`/`Vec<u8>` or `Path`/`PathBuf`.
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+
+use MaybeOwned;
+
+macro_rules! unsized_pair_impls {
+    ($(#[$meta:meta])* $Name:ident, $Borrowed:ty, $Owned:ty) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub enum $Name<'a> {
+            /// owns the data
+            Owned($Owned),
+            /// has a reference to the data
+            Borrowed(&'a $Borrowed),
+        }
+
+        impl $Name<'_> {
+            /// Returns true if the data is owned else false.
+            pub fn is_owned(&self) -> bool {
+                match self {
+                    Self::Owned(_) => true,
+                    Self::Borrowed(_) => false,
+                }
+            }
+
+            /// Return the contained data in it's owned form.
+            ///
+            /// If it's borrowed this will clone it.
+            pub fn into_owned(self) -> $Owned {
+                match self {
+                    Self::Owned(v) => v,
+                    Self::Borrowed(v) => v.to_owned(),
+                }
+            }
+
+            /// Internally converts it into it's owned variant and returns
+            /// a `&mut` to the owned data.
+            pub fn make_owned(&mut self) -> &mut $Owned {
+                if let Self::Borrowed(v) = self {
+                    *self = Self::Owned(v.to_owned());
+                }
+                match self {
+                    Self::Owned(v) => v,
+                    Self::Borrowed(..) => unreachable!(),
+                }
+            }
+        }
+
+        impl Deref for $Name<'_> {
+            type Target = $Borrowed;
+
+            fn deref(&self) -> &$Borrowed {
+                match self {
+                    Self::Owned(v) => v,
+                    Self::Borrowed(v) => v,
+                }
+            }
+        }
+
+        impl AsRef<$Borrowed> for $Name<'_> {
+            fn as_ref(&self) -> &$Borrowed {
+                self
+            }
+        }
+
+        impl Borrow<$Borrowed> for $Name<'_> {
+            fn borrow(&self) -> &$Borrowed {
+                self
+            }
+        }
+
+        impl<'a> From<&'a $Borrowed> for $Name<'a> {
+            fn from(v: &'a $Borrowed) -> Self {
+                Self::Borrowed(v)
+            }
+        }
+
+        impl From<$Owned> for $Name<'_> {
+            fn from(v: $Owned) -> Self {
+                Self::Owned(v)
+            }
+        }
+
+        /// Moves owned data and borrows the unsized data of borrowed data.
+        impl<'a> From<MaybeOwned<'a, $Owned>> for $Name<'a> {
+            fn from(v: MaybeOwned<'a, $Owned>) -> Self {
+                match v {
+                    MaybeOwned::Owned(v) => Self::Owned(v),
+                    MaybeOwned::Borrowed(v) => Self::Borrowed(v.borrow()),
+                }
+            }
+        }
+
+        impl Default for $Name<'_> {
+            fn default() -> Self {
+                Self::Owned(<$Owned>::default())
+            }
+        }
+
+        impl PartialEq for $Name<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                **self == **other
+            }
+        }
+
+        impl Eq for $Name<'_> {}
+
+        impl PartialOrd for $Name<'_> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $Name<'_> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                (**self).cmp(&**other)
+            }
+        }
+
+        impl Hash for $Name<'_> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                (**self).hash(state)
+            }
+        }
+    };
+}
+
+unsized_pair_impls!(
+    /// Either owns a `String` or borrows a `str`.
+    ///
+    /// Unlike `MaybeOwned<String>`, which would borrow a `&String`, this
+    /// borrows a `&str`, so e.g. string literals can be borrowed. With the
+    /// `serde` feature it borrows the input when deserializing if possible.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use maybe_owned::MaybeOwnedStr;
+    ///
+    /// let mut name = MaybeOwnedStr::from("static");
+    /// assert!(!name.is_owned());
+    /// name.make_owned().push_str(" and owned");
+    /// assert_eq!(name.to_string(), "static and owned");
+    /// ```
+    MaybeOwnedStr,
+    str,
+    String
+);
+
+impl fmt::Display for MaybeOwnedStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+unsized_pair_impls!(
+    /// Either owns a `Vec<u8>` or borrows a `[u8]`.
+    ///
+    /// Unlike `MaybeOwned<Vec<u8>>`, which would borrow a `&Vec<u8>`, this
+    /// borrows a `&[u8]`. With the `serde` feature it's serialized as bytes
+    /// and borrows the input when deserializing if possible.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use maybe_owned::MaybeOwnedBytes;
+    ///
+    /// let mut buf = MaybeOwnedBytes::from(&b"abc"[..]);
+    /// assert!(!buf.is_owned());
+    /// buf.make_owned().push(b'd');
+    /// assert_eq!(&*buf, b"abcd");
+    /// ```
+    MaybeOwnedBytes,
+    [u8],
+    Vec<u8>
+);
+
+#[cfg(feature = "std")]
+unsized_pair_impls!(
+    /// Either owns a `PathBuf` or borrows a `Path`.
+    ///
+    /// Unlike `MaybeOwned<PathBuf>`, which would borrow a `&PathBuf`, this
+    /// borrows a `&Path`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use maybe_owned::MaybeOwnedPath;
+    ///
+    /// let mut path = MaybeOwnedPath::from(Path::new("/tmp"));
+    /// assert!(!path.is_owned());
+    /// path.make_owned().push("file");
+    /// assert_eq!(&*path, Path::new("/tmp/file"));
+    /// ```
+    MaybeOwnedPath,
+    Path,
+    PathBuf
+);
+
+/// Displays the path like `Path::display`.
+#[cfg(feature = "std")]
+impl fmt::Display for MaybeOwnedPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.display(), f)
+    }
+}
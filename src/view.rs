@@ -0,0 +1,49 @@
+//! A read-only view of the state of a `MaybeOwned` or `MaybeOwnedMut`.
+use core::ops::Deref;
+
+/// The borrow state and data of a `MaybeOwned` or `MaybeOwnedMut` as
+/// returned by [`MaybeOwned::view`](crate::MaybeOwned::view).
+///
+/// Matching on this instead of the variants of `MaybeOwned` keeps working
+/// even if the internal representation of `MaybeOwned` changes.
+///
+/// ## Example
+///
+/// ```
+/// use maybe_owned::{MaybeOwned, MaybeOwnedView};
+///
+/// let data = 12u8;
+/// let maybe = MaybeOwned::Borrowed(&data);
+/// match maybe.view() {
+///     MaybeOwnedView::Owned(_) => unreachable!(),
+///     MaybeOwnedView::Borrowed(v) => assert_eq!(*v, 12),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaybeOwnedView<'s, T: 's> {
+    /// the data is owned
+    Owned(&'s T),
+    /// the data is borrowed
+    Borrowed(&'s T),
+}
+
+impl<T> MaybeOwnedView<'_, T> {
+    /// Returns true if the data is owned else false.
+    pub fn is_owned(&self) -> bool {
+        match self {
+            MaybeOwnedView::Owned(_) => true,
+            MaybeOwnedView::Borrowed(_) => false,
+        }
+    }
+}
+
+impl<T> Deref for MaybeOwnedView<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            MaybeOwnedView::Owned(v) => v,
+            MaybeOwnedView::Borrowed(v) => v,
+        }
+    }
+}
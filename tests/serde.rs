@@ -0,0 +1,150 @@
+#![cfg(all(feature = "serde", feature = "alloc"))]
+extern crate maybe_owned;
+extern crate serde;
+extern crate serde_json;
+
+use std::cell::Cell;
+
+use maybe_owned::serde_tagged;
+use maybe_owned::{serialize_owned, MaybeOwned, MaybeOwnedBytes, MaybeOwnedStr};
+use serde::{Serialize, Serializer};
+
+/// Serializes to the number of times it was cloned.
+struct CloneCounter<'c>(&'c Cell<usize>);
+
+impl Clone for CloneCounter<'_> {
+    fn clone(&self) -> Self {
+        self.0.set(self.0.get() + 1);
+        CloneCounter(self.0)
+    }
+}
+
+impl Serialize for CloneCounter<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.get().serialize(serializer)
+    }
+}
+
+fn to_json(maybe: &MaybeOwned<'_, CloneCounter<'_>>) -> String {
+    let mut out = Vec::new();
+    serialize_owned(maybe, &mut serde_json::Serializer::new(&mut out)).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn serialize_owned_clones_borrowed() {
+    let clones = Cell::new(0);
+    let data = CloneCounter(&clones);
+    assert_eq!(to_json(&MaybeOwned::Borrowed(&data)), "1");
+    assert_eq!(clones.get(), 1);
+}
+
+#[test]
+fn serialize_owned_does_not_clone_owned() {
+    let clones = Cell::new(0);
+    assert_eq!(to_json(&MaybeOwned::Owned(CloneCounter(&clones))), "0");
+    assert_eq!(clones.get(), 0);
+}
+
+#[test]
+fn bytes_round_trip() {
+    let data = [1u8, 2, 3];
+    let json = serde_json::to_string(&MaybeOwnedBytes::from(&data[..])).unwrap();
+    assert_eq!(json, "[1,2,3]");
+    let bytes: MaybeOwnedBytes = serde_json::from_str(&json).unwrap();
+    assert!(bytes.is_owned());
+    assert_eq!(&*bytes, &data);
+}
+
+#[test]
+fn bytes_borrow_input() {
+    let json = String::from(r#""abc""#);
+    let bytes: MaybeOwnedBytes = serde_json::from_str(&json).unwrap();
+    assert!(!bytes.is_owned());
+    assert_eq!(&*bytes, b"abc");
+}
+
+#[test]
+fn str_borrows_input() {
+    let json = String::from(r#"{"a": "abc", "b": "a\"c"}"#);
+    let map: std::collections::BTreeMap<MaybeOwnedStr, MaybeOwnedStr> =
+        serde_json::from_str(&json).unwrap();
+    let a = &map[&MaybeOwnedStr::from("a")];
+    assert!(!a.is_owned());
+    assert_eq!(&**a, "abc");
+    assert!(std::ptr::eq(a.as_ptr(), json[7..].as_ptr()));
+
+    // escape sequences can't be borrowed
+    let b = &map[&MaybeOwnedStr::from("b")];
+    assert!(b.is_owned());
+    assert_eq!(&**b, "a\"c");
+}
+
+#[test]
+fn str_round_trip() {
+    let json = serde_json::to_string(&MaybeOwnedStr::from("abc")).unwrap();
+    assert_eq!(json, r#""abc""#);
+    let s: MaybeOwnedStr = serde_json::from_str(&json).unwrap();
+    assert_eq!(&*s, "abc");
+}
+
+#[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+struct Tagged<'a> {
+    #[serde(with = "serde_tagged")]
+    value: MaybeOwned<'a, u32>,
+}
+
+#[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+struct Transparent<'a> {
+    value: MaybeOwned<'a, u32>,
+}
+
+#[test]
+fn transparent_round_trip() {
+    let value = 12;
+    let json = serde_json::to_string(&Transparent {
+        value: MaybeOwned::Borrowed(&value),
+    })
+    .unwrap();
+    assert_eq!(json, r#"{"value":12}"#);
+    let back: Transparent = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.value, MaybeOwned::Owned(12));
+}
+
+#[test]
+fn tagged_round_trip() {
+    let value = 12;
+    let json = serde_json::to_string(&Tagged {
+        value: MaybeOwned::Borrowed(&value),
+    })
+    .unwrap();
+    assert_eq!(json, r#"{"value":{"Borrowed":12}}"#);
+    let back: Tagged = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.value, MaybeOwned::Owned(12));
+
+    let json = serde_json::to_string(&Tagged {
+        value: MaybeOwned::Owned(13),
+    })
+    .unwrap();
+    assert_eq!(json, r#"{"value":{"Owned":13}}"#);
+    let back: Tagged = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.value, MaybeOwned::Owned(13));
+}
+
+#[test]
+fn tagged_deserialize_tag() {
+    let mut de = serde_json::Deserializer::from_str(r#"{"Borrowed":12}"#);
+    assert_eq!(
+        serde_tagged::deserialize_tag(&mut de).unwrap(),
+        (false, 12u32)
+    );
+
+    let mut de = serde_json::Deserializer::from_str(r#"{"Owned":13}"#);
+    assert_eq!(
+        serde_tagged::deserialize_tag(&mut de).unwrap(),
+        (true, 13u32)
+    );
+
+    let mut de = serde_json::Deserializer::from_str(r#"{"Shared":13}"#);
+    assert!(serde_tagged::deserialize_tag::<u32, _>(&mut de).is_err());
+}
@@ -0,0 +1,11 @@
+extern crate maybe_owned;
+
+use maybe_owned::MaybeOwned;
+
+fn keep<'b>(data: &u8) -> MaybeOwned<'b, u8> {
+    MaybeOwned::Borrowed(data).assert_outlives::<'b>()
+}
+
+fn main() {
+    keep(&1);
+}
@@ -0,0 +1,17 @@
+extern crate maybe_owned;
+
+use maybe_owned::MaybeOwnedMut;
+
+struct Data(u8);
+
+fn register<'a, E>(value: E) -> MaybeOwnedMut<'a, Data>
+where
+    E: Into<MaybeOwnedMut<'a, Data>>,
+{
+    value.into()
+}
+
+fn main() {
+    let data = Data(1);
+    register(&data);
+}
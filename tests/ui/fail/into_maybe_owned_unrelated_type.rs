@@ -0,0 +1,16 @@
+extern crate maybe_owned;
+
+use maybe_owned::MaybeOwned;
+
+struct Data(u8);
+
+fn register<'a, E>(value: E) -> MaybeOwned<'a, Data>
+where
+    E: Into<MaybeOwned<'a, Data>>,
+{
+    value.into()
+}
+
+fn main() {
+    register(12u8);
+}
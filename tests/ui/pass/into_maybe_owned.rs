@@ -0,0 +1,21 @@
+extern crate maybe_owned;
+
+use maybe_owned::MaybeOwned;
+
+struct Data(u8);
+
+fn register<'a, E>(value: E) -> MaybeOwned<'a, Data>
+where
+    E: Into<MaybeOwned<'a, Data>>,
+{
+    value.into()
+}
+
+fn main() {
+    let data = Data(1);
+    assert!(register(Data(2)).is_owned());
+    assert!(!register(&data).is_owned());
+    assert!(register(MaybeOwned::Owned(Data(3))).is_owned());
+    assert!(!register(MaybeOwned::Borrowed(&data)).is_owned());
+    assert_eq!(register(&data).0, 1);
+}